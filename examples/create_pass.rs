@@ -16,7 +16,7 @@ fn main() {
             "vxwxd7J8AlNNFPS8k0a0FfUFtq0ewzFdc",
             "https://example.com/passes/",
         ).relevant_date("2018-11-25T14:25-08:00".into())
-        .add_location((-122.3748889, 37.6189722))
+        .add_location(Location::new(37.6189722, -122.3748889))
         .add_barcode((BarcodeFormat::Code128, "FOOBAR BAZBAF 193197"))
         .organization_name("Surface Lines")
         .description("Surface Lines Pass")
@@ -31,8 +31,9 @@ fn main() {
         PassSource::new("/Users/sergeysova/Projects/passkit/examples/BoardingPass.pass/");
 
     source.add_pass(pass);
-    if let Err(error) = source.build_pkpass() {
-        panic!("Example failed: {}", error);
+    match source.build_pkpass() {
+        Ok(pkpass) => fs::write("BoardingPass.pkpass", pkpass).unwrap(),
+        Err(error) => panic!("Example failed: {}", error),
     }
 
     // println!("{:#?}", source);
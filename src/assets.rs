@@ -0,0 +1,104 @@
+use std::fs;
+use std::path;
+use walk::source_files;
+use {get_hash, read_file_to_vec, IgnoreRules, Manifest, PassError, PassResult};
+
+/// Files that are produced for every pass and never belong to the shared assets
+const PER_PASS_FILES: [&str; 4] = [
+    "pass.json",
+    "personalization.json",
+    "manifest.json",
+    "signature",
+];
+
+/// Images of a .pass directory, read and hashed once and reused between builds
+///
+/// Useful when many passes are issued from the same design:
+/// only pass.json has to be hashed for every new pass.
+#[derive(Debug, Default, Clone)]
+pub struct PreparedAssets {
+    /// place where images was read from
+    source_directory: String,
+
+    /// file names with their contents
+    files: Vec<(String, Vec<u8>)>,
+
+    /// hashes of files for manifest.json
+    manifest: Manifest,
+}
+
+impl PreparedAssets {
+    /// Read and hash every file from the source directory except pass.json and personalization.json
+    pub fn prepare<S: Into<String>>(source: S) -> PassResult<PreparedAssets> {
+        Self::prepare_with(source, &IgnoreRules::default())
+    }
+
+    /// Same as `prepare`, but skips files by custom rules
+    pub fn prepare_with<S: Into<String>>(
+        source: S,
+        rules: &IgnoreRules,
+    ) -> PassResult<PreparedAssets> {
+        let source_directory = source.into();
+        let mut assets = PreparedAssets {
+            source_directory,
+            ..Default::default()
+        };
+
+        let source = path::Path::new(&assets.source_directory);
+        for file in source_files(source, rules)? {
+            if PER_PASS_FILES.contains(&file.name.as_str()) {
+                continue;
+            }
+
+            let content = read_file_to_vec(&file.path).map_err(PassError::read(file.path))?;
+
+            assets.manifest.insert(file.name.clone(), get_hash(&content));
+            assets.files.push((file.name, content));
+        }
+
+        Ok(assets)
+    }
+
+    /// Directory assets was prepared from
+    pub fn source_directory(&self) -> &str {
+        &self.source_directory
+    }
+
+    /// Precalculated hashes of the assets
+    pub fn manifest(&self) -> &Manifest {
+        &self.manifest
+    }
+
+    /// File names with their contents
+    pub(crate) fn files(&self) -> &[(String, Vec<u8>)] {
+        &self.files
+    }
+
+    /// Write contents of the assets to directory without reading source again
+    pub(crate) fn write_to(&self, dir: &path::Path) -> PassResult<()> {
+        for (file_name, content) in &self.files {
+            let target = dir.join(file_name);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent).map_err(PassError::write(parent))?;
+            }
+            fs::write(&target, content).map_err(PassError::write(target))?;
+        }
+        Ok(())
+    }
+}
+
+mod test {
+    #[test]
+    fn prepare_example_assets() {
+        use super::*;
+
+        let source = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/BoardingPass.pass");
+        let assets = PreparedAssets::prepare(source).unwrap();
+
+        let manifest = assets.manifest();
+        assert_eq!(manifest.len(), 4);
+        assert_eq!(manifest["icon.png"].len(), 40);
+        assert!(manifest.contains_key("logo@2x.png"));
+        assert!(!manifest.contains_key("pass.json"));
+    }
+}
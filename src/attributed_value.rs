@@ -0,0 +1,215 @@
+use util::escape_html;
+use {PassError, PassResult};
+
+/// URL schemes Wallet opens from back field links
+const LINK_SCHEMES: [&str; 4] = ["http://", "https://", "mailto:", "tel:"];
+
+/// Attributed value composed of text and links, with everything else escaped.
+/// Only `<a href>` is rendered by Wallet, so user data can't inject other markup.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AttributedValue {
+    html: String,
+    text: String,
+}
+
+impl AttributedValue {
+    pub fn new() -> AttributedValue {
+        Default::default()
+    }
+
+    pub fn text<T: AsRef<str>>(mut self, text: T) -> AttributedValue {
+        self.html.push_str(&escape_html(text.as_ref()));
+        self.text.push_str(text.as_ref());
+        self
+    }
+
+    /// Link with the text. URLs with other schemes than http, https, mailto and tel
+    /// are not opened by Wallet, their text is added without link.
+    pub fn link<T, U>(mut self, text: T, url: U) -> AttributedValue
+    where
+        T: AsRef<str>,
+        U: AsRef<str>,
+    {
+        if !has_link_scheme(url.as_ref()) {
+            return self.text(text);
+        }
+        self.html.push_str(&format!(
+            "<a href='{}'>{}</a>",
+            escape_html(url.as_ref()),
+            escape_html(text.as_ref())
+        ));
+        self.text.push_str(text.as_ref());
+        self
+    }
+
+    /// Value for `Field::attributed_value`
+    pub fn html(&self) -> &str {
+        &self.html
+    }
+
+    /// Text without markup, for `Field::value` shown in notifications
+    pub fn plain_text(&self) -> &str {
+        &self.text
+    }
+}
+
+/// Check that attributed value has no markup besides `<a href>` links
+pub fn validate_attributed_value(value: &str) -> PassResult<()> {
+    let mut rest = value;
+    while let Some(start) = rest.find('<') {
+        let length = link_length(&rest[start..]).map_err(PassError::InvalidAttributedValue)?;
+        rest = &rest[start + length..];
+    }
+    Ok(())
+}
+
+/// Keep valid links and escape all other markup of attributed value
+pub fn sanitize_attributed_value(value: &str) -> String {
+    let mut sanitized = String::with_capacity(value.len());
+    let mut rest = value;
+    while !rest.is_empty() {
+        let start = rest.find('<').unwrap_or(rest.len());
+        sanitized.push_str(&escape_text(&rest[..start]));
+        rest = &rest[start..];
+        if rest.is_empty() {
+            break;
+        }
+        match link_length(rest) {
+            Ok(length) => {
+                sanitized.push_str(&rest[..length]);
+                rest = &rest[length..];
+            }
+            Err(_) => {
+                sanitized.push_str("&lt;");
+                rest = &rest[1..];
+            }
+        }
+    }
+    sanitized
+}
+
+/// Length of `<a href='url'>text</a>` at the start of value
+fn link_length(value: &str) -> Result<usize, &'static str> {
+    let tag_end = value.find('>').ok_or("unclosed tag")?;
+    let tag = &value[1..tag_end];
+    let lowercase = tag.to_ascii_lowercase();
+    if lowercase.starts_with('/') {
+        return Err("closing tag without link");
+    }
+    if !lowercase.starts_with('a') || !lowercase[1..].starts_with(char::is_whitespace) {
+        return Err("only <a> tag is supported");
+    }
+
+    let attribute = tag[1..].trim();
+    if !attribute.to_ascii_lowercase().starts_with("href") {
+        return Err("only href attribute is supported");
+    }
+    let quoted = attribute[4..].trim_start();
+    if !quoted.starts_with('=') {
+        return Err("only href attribute is supported");
+    }
+    let quoted = quoted[1..].trim_start();
+    let quote = match quoted.chars().next() {
+        Some(quote @ '\'') | Some(quote @ '"') => quote,
+        _ => return Err("href must be quoted"),
+    };
+    let url_end = quoted[1..].find(quote).ok_or("href must be quoted")?;
+    if !quoted[1 + url_end + 1..].trim().is_empty() {
+        return Err("only href attribute is supported");
+    }
+    if !has_link_scheme(&quoted[1..=url_end]) {
+        return Err("link must use http, https, mailto or tel URL");
+    }
+
+    let content = &value[tag_end + 1..];
+    let text_end = content.find('<').ok_or("unclosed link")?;
+    if !content[text_end..].to_ascii_lowercase().starts_with("</a>") {
+        return Err("links can contain only text");
+    }
+    Ok(tag_end + 1 + text_end + "</a>".len())
+}
+
+fn has_link_scheme(url: &str) -> bool {
+    let url = url.trim().to_ascii_lowercase();
+    LINK_SCHEMES.iter().any(|scheme| url.starts_with(scheme))
+}
+
+/// Escape text, keeping character references like `&amp;`
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for (index, char) in text.char_indices() {
+        match char {
+            '&' if !is_reference(&text[index..]) => escaped.push_str("&amp;"),
+            '>' => escaped.push_str("&gt;"),
+            _ => escaped.push(char),
+        }
+    }
+    escaped
+}
+
+fn is_reference(text: &str) -> bool {
+    match text[1..].find(';') {
+        Some(end) if end > 0 => {
+            let name = &text[1..=end];
+            let name = name.strip_prefix('#').unwrap_or(name);
+            name.chars().all(|char| char.is_ascii_alphanumeric())
+        }
+        _ => false,
+    }
+}
+
+mod test {
+    #[test]
+    fn builder_escapes_text() {
+        use super::*;
+
+        let value = AttributedValue::new()
+            .text("Questions? <b>Call</b> ")
+            .link("+1 (800) 555-0100", "tel:+18005550100")
+            .text(" or ")
+            .link("us & them", "https://example.com/?a=1&b='2'")
+            .link("script", "javascript:alert(1)");
+        assert_eq!(
+            value.html(),
+            "Questions? &lt;b&gt;Call&lt;/b&gt; <a href='tel:+18005550100'>+1 (800) 555-0100</a> or \
+             <a href='https://example.com/?a=1&amp;b=&#39;2&#39;'>us &amp; them</a>script"
+        );
+        assert_eq!(
+            value.plain_text(),
+            "Questions? <b>Call</b> +1 (800) 555-0100 or us & themscript"
+        );
+        assert!(validate_attributed_value(value.html()).is_ok());
+    }
+
+    #[test]
+    fn validate_and_sanitize() {
+        use super::*;
+
+        let valid = "<a href='http://example.com/customers/123'>Edit my profile</a> &amp; more";
+        assert!(validate_attributed_value(valid).is_ok());
+        assert!(validate_attributed_value("<A HREF = \"mailto:me@example.com\">Mail</A>").is_ok());
+        assert_eq!(sanitize_attributed_value(valid), valid);
+
+        for invalid in &[
+            "<b>bold</b>",
+            "<a href='https://example.com' onclick='x()'>link</a>",
+            "<a href='javascript:alert(1)'>link</a>",
+            "<a href='https://example.com'><b>link</b></a>",
+            "<a href='https://example.com'>link",
+            "text </a>",
+            "1 < 2",
+        ] {
+            assert!(validate_attributed_value(invalid).is_err(), "{}", invalid);
+            let sanitized = sanitize_attributed_value(invalid);
+            assert!(
+                validate_attributed_value(&sanitized).is_ok(),
+                "{}",
+                sanitized
+            );
+        }
+        assert_eq!(
+            sanitize_attributed_value("<img src=x> & <a href='https://example.com'>ok</a>"),
+            "&lt;img src=x&gt; &amp; <a href='https://example.com'>ok</a>"
+        );
+    }
+}
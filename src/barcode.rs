@@ -0,0 +1,175 @@
+use pass::{Barcode, BarcodeFormat, Pass, VisualAppearance};
+use {PassError, PassResult};
+
+/// Longest Code 128 message which still fits the width of the pass
+const CODE128_MAX_LENGTH: usize = 80;
+
+impl BarcodeFormat {
+    /// Maximum count of message bytes the format can hold
+    pub fn capacity(&self) -> usize {
+        match self {
+            // version 40, error correction level M, byte mode
+            BarcodeFormat::QR => 2331,
+            // byte compaction, 863 codewords with the recommended error correction level 5
+            BarcodeFormat::PDF417 => 1034,
+            // 32 layers, binary mode, bytes stuffed in every word, 33% error correction
+            BarcodeFormat::Aztec => 1752,
+            BarcodeFormat::Code128 => CODE128_MAX_LENGTH,
+        }
+    }
+}
+
+impl Barcode {
+    /// Message converted to bytes with `message_encoding`
+    pub fn encoded_message(&self) -> PassResult<Vec<u8>> {
+        match self.message_encoding.to_ascii_lowercase().as_str() {
+            "iso-8859-1" | "latin1" => self
+                .message
+                .chars()
+                .map(|c| {
+                    if (c as u32) <= 0xFF {
+                        Ok(c as u8)
+                    } else {
+                        Err(PassError::InvalidBarcode(
+                            "message has characters outside of iso-8859-1",
+                        ))
+                    }
+                })
+                .collect(),
+            "us-ascii" | "ascii" if !self.message.is_ascii() => Err(PassError::InvalidBarcode(
+                "message has characters outside of us-ascii",
+            )),
+            "us-ascii" | "ascii" | "utf-8" => Ok(self.message.as_bytes().to_vec()),
+            _ => Err(PassError::InvalidBarcode("unsupported message encoding")),
+        }
+    }
+
+    /// Check that message is representable in the encoding and fits the format
+    pub fn validate(&self) -> PassResult<()> {
+        let encoded = self.encoded_message()?;
+        if let BarcodeFormat::Code128 = self.format {
+            if !encoded.iter().all(u8::is_ascii) {
+                return Err(PassError::InvalidBarcode(
+                    "Code 128 message must contain only ASCII characters",
+                ));
+            }
+        }
+        if encoded.len() > self.format.capacity() {
+            return Err(PassError::InvalidBarcode(
+                "message doesn't fit the barcode format",
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Pass {
+    /// Barcodes of the pass, in order of preference
+    pub fn barcodes(&self) -> &[Barcode] {
+        self.visual
+            .as_ref()
+            .map_or(&[], |visual| visual.barcodes.as_slice())
+    }
+}
+
+impl VisualAppearance {
+    /// Copy first non-Code128 barcode to the legacy `barcode` key,
+    /// which iOS 8 and earlier read instead of `barcodes`
+    pub fn set_legacy_barcode(&mut self) {
+        self.barcode = self
+            .barcodes
+            .iter()
+            .find(|barcode| !matches!(barcode.format, BarcodeFormat::Code128))
+            .cloned();
+    }
+}
+
+/// Check that every barcode message is representable and fits its format
+pub fn validate_barcodes(barcodes: &[Barcode]) -> PassResult<()> {
+    for barcode in barcodes {
+        barcode.validate()?;
+    }
+    Ok(())
+}
+
+impl Pass {
+    /// iOS 8 and earlier don't display Code 128, so it needs a QR, PDF417 or Aztec fallback.
+    /// Newer devices show Code 128 alone, so this is checked only by `Pass::validate`.
+    pub(crate) fn barcode_violations(&self) -> Vec<String> {
+        let barcodes = self.barcodes();
+        let mut violations: Vec<String> = barcodes
+            .iter()
+            .map(Barcode::validate)
+            .filter_map(Result::err)
+            .map(|error| error.to_string())
+            .collect();
+
+        let is_code128 = |barcode: &Barcode| matches!(barcode.format, BarcodeFormat::Code128);
+        if barcodes.iter().any(is_code128) && barcodes.iter().all(is_code128) {
+            violations.push("Code 128 requires QR, PDF417 or Aztec fallback".to_string());
+        }
+        violations
+    }
+}
+
+mod test {
+    #[test]
+    fn message_encoding() {
+        use super::*;
+
+        let mut barcode = Barcode::from((BarcodeFormat::QR, "Café"));
+        assert_eq!(barcode.encoded_message().unwrap(), b"Caf\xe9");
+
+        barcode.message = "東京".into();
+        assert!(barcode.validate().is_err());
+
+        barcode.message_encoding = "utf-8".into();
+        assert!(barcode.validate().is_ok());
+
+        barcode.message_encoding = "shift_jis".into();
+        assert!(barcode.validate().is_err());
+    }
+
+    #[test]
+    fn format_capacity() {
+        use super::*;
+
+        let long = "0".repeat(2000);
+        assert!(Barcode::from((BarcodeFormat::QR, long.as_str()))
+            .validate()
+            .is_ok());
+        assert!(Barcode::from((BarcodeFormat::PDF417, long.as_str()))
+            .validate()
+            .is_err());
+        assert!(Barcode::from((BarcodeFormat::Code128, "Café"))
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn code128_fallback() {
+        use super::*;
+        use PassBuilder;
+
+        let code128 = Barcode::from((BarcodeFormat::Code128, "0001"));
+        let aztec = Barcode::from((BarcodeFormat::Aztec, "0001"));
+        assert!(validate_barcodes(std::slice::from_ref(&code128)).is_ok());
+
+        let builder = PassBuilder::new("0001", "pass.com.example", "CDHE9L6U22")
+            .organization_name("Coffee Shop")
+            .description("Rewards card")
+            .add_barcode(code128);
+        match builder.clone().finish_store_card().validate() {
+            Err(PassError::InvalidPass(violations)) => assert_eq!(
+                violations,
+                vec!["Code 128 requires QR, PDF417 or Aztec fallback"]
+            ),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(builder
+            .add_barcode(aztec)
+            .finish_store_card()
+            .validate()
+            .is_ok());
+    }
+}
@@ -0,0 +1,216 @@
+use field::Field;
+use pass::{FieldSection, Pass};
+use serde_json::Value as Json;
+
+/// Change of a single field, matched between passes by `Field::key`
+#[derive(Debug, Clone)]
+pub enum FieldChange {
+    Added {
+        section: FieldSection,
+        field: Field,
+    },
+    Removed {
+        section: FieldSection,
+        field: Field,
+    },
+    /// Field content or section differs, `section` is where the new field is placed
+    Changed {
+        section: FieldSection,
+        old: Field,
+        new: Field,
+    },
+}
+
+impl FieldChange {
+    pub fn key(&self) -> &str {
+        match self {
+            FieldChange::Added { field, .. } | FieldChange::Removed { field, .. } => &field.key,
+            FieldChange::Changed { new, .. } => &new.key,
+        }
+    }
+
+    /// Alert text Wallet shows for the change: `change_message` with `%@` replaced by the new value.
+    /// Wallet notifies only about changed values of fields having change message.
+    pub fn notification(&self) -> Option<String> {
+        match self {
+            FieldChange::Changed { old, new, .. } if value_text(old) != value_text(new) => new
+                .change_message
+                .as_ref()
+                .map(|message| message.replace("%@", &value_text(new))),
+            _ => None,
+        }
+    }
+}
+
+/// Differences between two versions of a pass
+#[derive(Debug, Clone, Default)]
+pub struct PassDiff {
+    pub fields: Vec<FieldChange>,
+
+    /// Any barcode was added, removed or changed
+    pub barcodes_changed: bool,
+
+    /// New `voided` value, if it changed
+    pub voided: Option<bool>,
+
+    /// Something else changed, e.g. colors, dates or locations
+    pub other_changed: bool,
+}
+
+impl PassDiff {
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+            && !self.barcodes_changed
+            && self.voided.is_none()
+            && !self.other_changed
+    }
+
+    /// Alerts shown to user after the update
+    pub fn notifications(&self) -> Vec<String> {
+        self.fields
+            .iter()
+            .filter_map(FieldChange::notification)
+            .collect()
+    }
+}
+
+impl Pass {
+    /// Compare the pass with its new version to decide whether to push an update
+    pub fn diff(&self, new: &Pass) -> PassDiff {
+        let old_fields = fields_of(self);
+        let new_fields = fields_of(new);
+
+        let mut fields = Vec::new();
+        for (section, field) in &new_fields {
+            match old_fields.iter().find(|(_, old)| old.key == field.key) {
+                Some((old_section, old)) => {
+                    if old_section != section || to_json(old) != to_json(field) {
+                        fields.push(FieldChange::Changed {
+                            section: *section,
+                            old: (*old).clone(),
+                            new: (*field).clone(),
+                        });
+                    }
+                }
+                None => fields.push(FieldChange::Added {
+                    section: *section,
+                    field: (*field).clone(),
+                }),
+            }
+        }
+        for (section, field) in &old_fields {
+            if !new_fields.iter().any(|(_, new)| new.key == field.key) {
+                fields.push(FieldChange::Removed {
+                    section: *section,
+                    field: (*field).clone(),
+                });
+            }
+        }
+
+        PassDiff {
+            fields,
+            barcodes_changed: to_json(&self.barcodes()) != to_json(&new.barcodes()),
+            voided: if self.voided != new.voided {
+                Some(new.voided)
+            } else {
+                None
+            },
+            other_changed: without_diffed_keys(self) != without_diffed_keys(new),
+        }
+    }
+}
+
+fn fields_of(pass: &Pass) -> Vec<(FieldSection, &Field)> {
+    let structure = pass.style.structure();
+    FieldSection::ALL
+        .iter()
+        .flat_map(|section| {
+            structure
+                .fields(*section)
+                .iter()
+                .map(move |field| (*section, field))
+        })
+        .collect()
+}
+
+fn value_text(field: &Field) -> String {
+    field
+        .attributed_value
+        .clone()
+        .unwrap_or_else(|| field.value.to_string())
+}
+
+fn to_json<T: ::serde::Serialize>(value: &T) -> Json {
+    serde_json::to_value(value).unwrap_or(Json::Null)
+}
+
+/// pass.json content without fields, barcodes and voided, which are compared separately
+fn without_diffed_keys(pass: &Pass) -> Json {
+    let mut json = to_json(pass);
+    if let Json::Object(pass) = &mut json {
+        for key in &["barcodes", "barcode", "voided"] {
+            pass.remove(*key);
+        }
+        for style in &[
+            "boardingPass",
+            "coupon",
+            "eventTicket",
+            "generic",
+            "storeCard",
+        ] {
+            if let Some(Json::Object(structure)) = pass.get_mut(*style) {
+                for section in &[
+                    "headerFields",
+                    "primaryFields",
+                    "secondaryFields",
+                    "auxiliaryFields",
+                    "backFields",
+                ] {
+                    structure.remove(*section);
+                }
+            }
+        }
+    }
+    json
+}
+
+mod test {
+    #[test]
+    fn gate_change() {
+        use super::*;
+        use {BarcodeFormat, PassBuilder, TransitType};
+
+        let builder = PassBuilder::new("0001", "pass.com.example", "CDHE9L6U22")
+            .add_primary_field(("depart", "SAN FRANCISCO", "SFO"))
+            .add_back_field(("terms", "TERMS", "Non refundable"))
+            .add_barcode((BarcodeFormat::QR, "0001"));
+        let old = builder
+            .clone()
+            .add_header_field(Field::new("GATE", "gate", "23", "Gate changed to %@."))
+            .finish_boarding_pass(TransitType::Air);
+        assert!(old.diff(&old).is_empty());
+
+        let field_only = builder
+            .clone()
+            .add_header_field(("gate", "GATE", "24"))
+            .finish_boarding_pass(TransitType::Air);
+        let diff = old.diff(&field_only);
+        assert!(!diff.other_changed && !diff.barcodes_changed);
+        assert!(diff.notifications().is_empty());
+
+        let new = builder
+            .add_header_field(Field::new("GATE", "gate", "F12", "Gate changed to %@."))
+            .add_secondary_field(("seat", "SEAT", "7A"))
+            .add_barcode((BarcodeFormat::Aztec, "0001"))
+            .background_color("rgb(0, 0, 0)")
+            .finish_boarding_pass(TransitType::Air);
+        let diff = old.diff(&new);
+
+        assert_eq!(diff.notifications(), vec!["Gate changed to F12."]);
+        let keys: Vec<&str> = diff.fields.iter().map(FieldChange::key).collect();
+        assert_eq!(keys, vec!["gate", "seat"]);
+        assert!(diff.barcodes_changed);
+        assert!(diff.other_changed);
+        assert_eq!(diff.voided, None);
+    }
+}
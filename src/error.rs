@@ -0,0 +1,296 @@
+use openssl::error::ErrorStack;
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::path;
+use zip::result::ZipError;
+use PersonalizationField;
+
+/// Stage of the pass processing where error happened
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ErrorKind {
+    /// Reading source files, writing temporary files and packing archive
+    Build,
+
+    /// Loading signing identity and signing manifest.json
+    Signing,
+
+    /// Pass content doesn't satisfy requirements
+    Validation,
+
+    /// Reading pass.json into Pass
+    Parsing,
+}
+
+/// Variants behind `render` and `tokio` features exist only when the feature is enabled,
+/// so matches outside of the crate need a wildcard arm
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum PassError {
+    /// Source file or directory can't be read
+    Read {
+        path: path::PathBuf,
+        source: io::Error,
+    },
+
+    /// File can't be written into temporary directory
+    Write {
+        path: path::PathBuf,
+        source: io::Error,
+    },
+
+    /// Symbolic link in the source directory points outside of it
+    SymlinkOutsideSource { path: path::PathBuf },
+
+    /// Temporary directory for package can't be created
+    CreateTempDir { source: io::Error },
+
+    /// File can't be serialized, e.g. pass.json or manifest.json
+    Serialize {
+        path: path::PathBuf,
+        source: serde_json::Error,
+    },
+
+    /// .pkpass archive can't be written
+    Package { source: ZipError },
+
+    /// Barcode image can't be encoded
+    #[cfg(feature = "render")]
+    EncodePng { source: png::EncodingError },
+
+    /// Blocking task of hashing, signing or packing was cancelled or panicked
+    #[cfg(feature = "tokio")]
+    Task { source: tokio::task::JoinError },
+
+    /// Storage of issued passes failed
+    Store {
+        source: Box<dyn Error + Send + Sync>,
+    },
+
+    /// Certificate or private key can't be loaded
+    Certificate { source: ErrorStack },
+
+    /// Signature of manifest.json or token can't be created
+    Sign { source: ErrorStack },
+
+    /// Value Added Services key can't be generated, loaded or encoded
+    VasKey { source: ErrorStack },
+
+    /// Neither pass.json found in source nor Pass provided
+    PassContentNotFound,
+
+    /// Pass with the serial number is not stored
+    PassNotFound(String),
+
+    /// Required keys are missing or not allowed for the pass style
+    InvalidPass(Vec<String>),
+
+    /// Pass with personalization.json doesn't meet Wallet requirements
+    InvalidPersonalization(&'static str),
+
+    /// NFC encryption public key is not a P-256 SubjectPublicKeyInfo
+    InvalidEncryptionPublicKey(&'static str),
+
+    /// Barcode message doesn't fit its format or encoding
+    InvalidBarcode(&'static str),
+
+    /// Attributed value has markup besides `<a href>` links
+    InvalidAttributedValue(&'static str),
+
+    /// Location or beacon can't be used for relevance
+    InvalidRelevance(&'static str),
+
+    /// Pass lacks data required by its Google Wallet type
+    InvalidGoogleWalletPass(&'static str),
+
+    /// VAS payload can't be decrypted with the merchant key
+    InvalidVasPayload(&'static str),
+
+    /// Required field is absent in the submitted personalization info
+    MissingPersonalizationField(PersonalizationField),
+
+    /// Request path doesn't match the web service endpoint
+    InvalidRequestPath(String),
+
+    /// Request body sent by Wallet can't be parsed
+    ParseRequest { source: serde_json::Error },
+
+    /// Ignore rule is not a valid glob pattern
+    IgnorePattern {
+        pattern: String,
+        source: glob::PatternError,
+    },
+
+    /// pass.json has invalid content
+    Parse {
+        path: path::PathBuf,
+        source: serde_json::Error,
+    },
+}
+
+#[deprecated(note = "use PassError instead")]
+pub type PassCreateError = PassError;
+
+pub(crate) type PassResult<T> = Result<T, PassError>;
+
+impl PassError {
+    pub fn kind(&self) -> ErrorKind {
+        use self::PassError::*;
+        match self {
+            Read { .. }
+            | Write { .. }
+            | SymlinkOutsideSource { .. }
+            | CreateTempDir { .. }
+            | Serialize { .. }
+            | Package { .. }
+            | Store { .. } => ErrorKind::Build,
+            #[cfg(feature = "render")]
+            EncodePng { .. } => ErrorKind::Build,
+            #[cfg(feature = "tokio")]
+            Task { .. } => ErrorKind::Build,
+            Certificate { .. } | Sign { .. } | VasKey { .. } => ErrorKind::Signing,
+            PassContentNotFound
+            | PassNotFound(_)
+            | InvalidPass(_)
+            | InvalidPersonalization(_)
+            | InvalidEncryptionPublicKey(_)
+            | InvalidBarcode(_)
+            | InvalidAttributedValue(_)
+            | InvalidRelevance(_)
+            | InvalidGoogleWalletPass(_)
+            | MissingPersonalizationField(_)
+            | InvalidRequestPath(_)
+            | IgnorePattern { .. } => ErrorKind::Validation,
+            Parse { .. } | ParseRequest { .. } | InvalidVasPayload(_) => ErrorKind::Parsing,
+        }
+    }
+
+    /// File caused the error, if any
+    pub fn path(&self) -> Option<&path::Path> {
+        use self::PassError::*;
+        match self {
+            Read { path, .. }
+            | Write { path, .. }
+            | SymlinkOutsideSource { path }
+            | Serialize { path, .. }
+            | Parse { path, .. } => Some(path),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn read<P: Into<path::PathBuf>>(path: P) -> impl FnOnce(io::Error) -> PassError {
+        let path = path.into();
+        move |source| PassError::Read { path, source }
+    }
+
+    pub(crate) fn write<P: Into<path::PathBuf>>(path: P) -> impl FnOnce(io::Error) -> PassError {
+        let path = path.into();
+        move |source| PassError::Write { path, source }
+    }
+}
+
+impl fmt::Display for PassError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use self::PassError::*;
+        match self {
+            Read { path, .. } => write!(f, "Can't read {}", path.display()),
+            Write { path, .. } => write!(f, "Can't write {}", path.display()),
+            SymlinkOutsideSource { path } => {
+                write!(f, "{} links outside of source directory", path.display())
+            }
+            CreateTempDir { .. } => write!(f, "Can't create temporary directory. Check rights"),
+            Serialize { path, .. } => write!(f, "Can't serialize {}", path.display()),
+            Package { .. } => write!(f, "Can't create .pkpass archive"),
+            #[cfg(feature = "render")]
+            EncodePng { .. } => write!(f, "Can't encode barcode image"),
+            #[cfg(feature = "tokio")]
+            Task { .. } => write!(f, "Blocking task failed"),
+            Store { .. } => write!(f, "Pass storage failed"),
+            Certificate { .. } => write!(f, "Can't read signing certificate"),
+            Sign { .. } => write!(f, "Can't create signature"),
+            VasKey { .. } => write!(f, "Can't process NFC encryption key"),
+            PassContentNotFound => write!(
+                f,
+                "Please, provide pass.json or instance of Pass with add_pass() method"
+            ),
+            PassNotFound(serial_number) => write!(f, "Pass {} not found", serial_number),
+            InvalidPass(violations) => write!(f, "Invalid pass: {}", violations.join(", ")),
+            InvalidPersonalization(reason) => write!(f, "Pass can't be personalized: {}", reason),
+            InvalidEncryptionPublicKey(reason) => {
+                write!(f, "Invalid NFC encryption public key: {}", reason)
+            }
+            InvalidBarcode(reason) => write!(f, "Invalid barcode: {}", reason),
+            InvalidAttributedValue(reason) => write!(f, "Invalid attributed value: {}", reason),
+            InvalidRelevance(reason) => write!(f, "Invalid relevance: {}", reason),
+            InvalidGoogleWalletPass(reason) => {
+                write!(f, "Can't convert to Google Wallet pass: {}", reason)
+            }
+            InvalidVasPayload(reason) => write!(f, "Invalid VAS payload: {}", reason),
+            MissingPersonalizationField(field) => {
+                write!(f, "Personalization field {:?} is required", field)
+            }
+            InvalidRequestPath(path) => write!(f, "Unexpected request path {}", path),
+            ParseRequest { .. } => write!(f, "Request body invalid"),
+            IgnorePattern { pattern, .. } => write!(f, "Invalid ignore pattern {}", pattern),
+            Parse { path, .. } => write!(f, "{} invalid", path.display()),
+        }
+    }
+}
+
+impl Error for PassError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        use self::PassError::*;
+        match self {
+            Read { source, .. } | Write { source, .. } | CreateTempDir { source } => Some(source),
+            Serialize { source, .. } | Parse { source, .. } | ParseRequest { source } => {
+                Some(source)
+            }
+            Package { source } => Some(source),
+            #[cfg(feature = "render")]
+            EncodePng { source } => Some(source),
+            #[cfg(feature = "tokio")]
+            Task { source } => Some(source),
+            Store { source } => Some(source.as_ref()),
+            Certificate { source } | Sign { source } | VasKey { source } => Some(source),
+            IgnorePattern { source, .. } => Some(source),
+            SymlinkOutsideSource { .. }
+            | PassContentNotFound
+            | PassNotFound(_)
+            | InvalidPass(_)
+            | InvalidPersonalization(_)
+            | InvalidEncryptionPublicKey(_)
+            | InvalidBarcode(_)
+            | InvalidAttributedValue(_)
+            | InvalidRelevance(_)
+            | InvalidGoogleWalletPass(_)
+            | InvalidVasPayload(_)
+            | MissingPersonalizationField(_)
+            | InvalidRequestPath(_) => None,
+        }
+    }
+}
+
+mod test {
+    #[test]
+    fn parse_error_keeps_source() {
+        use super::*;
+
+        let source = serde_json::from_str::<::Pass>("{}").unwrap_err();
+        let error = PassError::Parse {
+            path: "BoardingPass.pass/pass.json".into(),
+            source,
+        };
+
+        assert_eq!(error.kind(), ErrorKind::Parsing);
+        assert_eq!(
+            error.path(),
+            Some(path::Path::new("BoardingPass.pass/pass.json"))
+        );
+        assert_eq!(error.to_string(), "BoardingPass.pass/pass.json invalid");
+        assert!(error
+            .source()
+            .unwrap()
+            .to_string()
+            .contains("missing field"));
+    }
+}
@@ -1,7 +1,12 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_derive::{Deserialize, Serialize};
+use std::fmt;
+use std::time::SystemTime;
 use util::*;
+use AttributedValue;
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(remote = "Self")]
 #[serde(rename_all = "camelCase")]
 #[serde(default)]
 pub struct Field {
@@ -42,13 +47,35 @@ pub struct Field {
     /// Value of the field, for example, `42`.
     pub value: Value,
 
+    /// Formatting of date value, `None` when the field has no date keys
     #[serde(flatten)]
     pub date: Option<FieldDate>,
 
+    /// Formatting of number value, `None` when the field has no number keys
     #[serde(flatten)]
     pub number: Option<FieldNumber>,
 }
 
+impl Serialize for Field {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Field::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Field {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut field = Field::deserialize(deserializer)?;
+        // flattened options are always deserialized, as each of their keys is optional
+        if field.date.as_ref().is_some_and(FieldDate::is_empty) {
+            field.date = None;
+        }
+        if field.number.as_ref().is_some_and(FieldNumber::is_empty) {
+            field.number = None;
+        }
+        Ok(field)
+    }
+}
+
 impl<TKey, TLabel, TValue> From<(TKey, TLabel, TValue)> for Field
 where
     TKey: Into<String>,
@@ -97,6 +124,16 @@ impl From<f64> for Value {
     }
 }
 
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::String(value) => write!(f, "{}", value),
+            Value::Int(value) => write!(f, "{}", value),
+            Value::Float(value) => write!(f, "{}", value),
+        }
+    }
+}
+
 impl Default for Value {
     fn default() -> Value {
         Value::String("".to_string())
@@ -147,7 +184,8 @@ impl TextAlignment {
 #[serde(rename_all = "camelCase")]
 pub struct FieldDate {
     /// Style of date to display
-    pub date_style: DateTimeStyle,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_style: Option<DateTimeStyle>,
 
     /// Always display the time and date in the given time zone, not in the user’s current time zone.
     /// The default value is false.
@@ -155,18 +193,28 @@ pub struct FieldDate {
     /// For backward compatibility with iOS 6, provide an appropriate time zone,
     /// so that the information is displayed meaningfully even without ignoring time zones.
     /// This key does not affect how relevance is calculated.
-    #[serde(skip_serializing_if = "is_false")]
-    pub ignores_time_zone: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ignores_time_zone: Option<bool>,
 
     /// If true, the label’s value is displayed as a relative date;
     /// otherwise, it is displayed as an absolute date.
     /// The default value is false.
     /// This key does not affect how relevance is calculated.
-    #[serde(skip_serializing_if = "is_false")]
-    pub is_relative: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_relative: Option<bool>,
 
     /// Style of time to display
-    pub time_style: DateTimeStyle,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_style: Option<DateTimeStyle>,
+}
+
+impl FieldDate {
+    pub fn is_empty(&self) -> bool {
+        self.date_style.is_none()
+            && self.ignores_time_zone.is_none()
+            && self.is_relative.is_none()
+            && self.time_style.is_none()
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -203,11 +251,19 @@ impl Default for DateTimeStyle {
 #[serde(rename_all = "camelCase")]
 pub struct FieldNumber {
     /// ISO 4217 currency code for the field’s value.
-    pub currency_code: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency_code: Option<String>,
 
     /// Style of number to display.
     /// Number styles have the same meaning as the Cocoa number formatter styles with corresponding names.
-    pub number_style: NumberStyle,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub number_style: Option<NumberStyle>,
+}
+
+impl FieldNumber {
+    pub fn is_empty(&self) -> bool {
+        self.currency_code.is_none() && self.number_style.is_none()
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -252,3 +308,273 @@ impl Field {
         }
     }
 }
+
+/// Builder keeping `value` consistent with date and number formatting keys
+#[derive(Debug, Clone)]
+pub struct FieldBuilder {
+    field: Field,
+}
+
+impl FieldBuilder {
+    /// Plain text field
+    pub fn new<K: Into<String>, V: Into<Value>>(key: K, value: V) -> FieldBuilder {
+        FieldBuilder {
+            field: Field {
+                key: key.into(),
+                value: value.into(),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Amount formatted in the currency, e.g. `("price", 12.5, "EUR")`
+    pub fn currency<K, C>(key: K, amount: f64, currency_code: C) -> FieldBuilder
+    where
+        K: Into<String>,
+        C: Into<String>,
+    {
+        let mut builder = FieldBuilder::new(key, amount);
+        builder.field.number = Some(FieldNumber {
+            currency_code: Some(currency_code.into()),
+            ..Default::default()
+        });
+        builder
+    }
+
+    pub fn number<K: Into<String>>(key: K, number: f64, style: NumberStyle) -> FieldBuilder {
+        let mut builder = FieldBuilder::new(key, number);
+        builder.field.number = Some(FieldNumber {
+            number_style: Some(style),
+            ..Default::default()
+        });
+        builder
+    }
+
+    /// Fraction displayed as percentage, `0.15` is shown as 15%
+    pub fn percentage<K: Into<String>>(key: K, fraction: f64) -> FieldBuilder {
+        FieldBuilder::number(key, fraction, NumberStyle::Percent)
+    }
+
+    /// Date and time displayed in the user's time zone, `DateTimeStyle::None` hides the part
+    pub fn date_time<K: Into<String>>(
+        key: K,
+        time: SystemTime,
+        date_style: DateTimeStyle,
+        time_style: DateTimeStyle,
+    ) -> FieldBuilder {
+        let mut builder = FieldBuilder::new(key, w3c_date(time));
+        builder.field.date = Some(FieldDate {
+            date_style: Some(date_style),
+            time_style: Some(time_style),
+            ..Default::default()
+        });
+        builder
+    }
+
+    /// Date displayed relative to now, e.g. "in 2 hours"
+    pub fn relative_date<K: Into<String>>(key: K, time: SystemTime) -> FieldBuilder {
+        let mut builder =
+            FieldBuilder::date_time(key, time, DateTimeStyle::Medium, DateTimeStyle::Short);
+        if let Some(date) = &mut builder.field.date {
+            date.is_relative = Some(true);
+        }
+        builder
+    }
+
+    /// Link shown with the text, `value` keeps the plain text for notifications
+    pub fn link<K, T, U>(key: K, text: T, url: U) -> FieldBuilder
+    where
+        K: Into<String>,
+        T: AsRef<str>,
+        U: AsRef<str>,
+    {
+        FieldBuilder::attributed(key, AttributedValue::new().link(text, url))
+    }
+
+    /// Text with links, `value` keeps the plain text for notifications
+    pub fn attributed<K: Into<String>>(key: K, value: AttributedValue) -> FieldBuilder {
+        let mut builder = FieldBuilder::new(key, value.plain_text());
+        builder.field.attributed_value = Some(value.html().to_string());
+        builder
+    }
+
+    pub fn label<L: Into<String>>(mut self, label: L) -> FieldBuilder {
+        self.field.label = Some(label.into());
+        self
+    }
+
+    /// Alert text on update, `%@` is replaced with the new value
+    pub fn change_message<M: Into<String>>(mut self, message: M) -> FieldBuilder {
+        self.field.change_message = Some(message.into());
+        self
+    }
+
+    pub fn text_alignment(mut self, alignment: TextAlignment) -> FieldBuilder {
+        self.field.text_alignment = alignment;
+        self
+    }
+
+    pub fn data_detector_types(mut self, types: Vec<DataDetectorType>) -> FieldBuilder {
+        self.field.data_detector_types = Some(types);
+        self
+    }
+
+    /// Display date in the time zone of the value, has no effect on fields without date
+    pub fn ignores_time_zone(mut self, ignores: bool) -> FieldBuilder {
+        if let Some(date) = &mut self.field.date {
+            date.ignores_time_zone = Some(ignores);
+        }
+        self
+    }
+
+    pub fn finish(self) -> Field {
+        self.field
+    }
+}
+
+impl From<FieldBuilder> for Field {
+    fn from(builder: FieldBuilder) -> Field {
+        builder.finish()
+    }
+}
+
+mod test {
+    #[test]
+    fn typed_fields() {
+        use super::*;
+        use std::time::{Duration, UNIX_EPOCH};
+
+        let price = FieldBuilder::currency("price", 12.5, "EUR")
+            .label("PRICE")
+            .finish();
+        let json = serde_json::to_value(&price).unwrap();
+        assert_eq!(json["value"], 12.5);
+        assert_eq!(json["currencyCode"], "EUR");
+        assert_eq!(json["label"], "PRICE");
+
+        let discount = serde_json::to_value(FieldBuilder::percentage("discount", 0.15).finish());
+        assert_eq!(discount.unwrap()["numberStyle"], "PKNumberStylePercent");
+
+        let departure = UNIX_EPOCH + Duration::from_secs(1_792_332_300);
+        let boarding = FieldBuilder::relative_date("boarding", departure)
+            .change_message("Boarding time changed to %@")
+            .finish();
+        let json = serde_json::to_value(&boarding).unwrap();
+        assert_eq!(json["value"], "2026-10-18T14:05:00Z");
+        assert_eq!(json["isRelative"], true);
+        assert_eq!(json["timeStyle"], "PKDateStyleShort");
+
+        let link = FieldBuilder::link("profile", "Edit <profile>", "https://example.com/?a=1&b=2")
+            .finish();
+        assert_eq!(link.value.to_string(), "Edit <profile>");
+        assert_eq!(
+            link.attributed_value.unwrap(),
+            "<a href='https://example.com/?a=1&amp;b=2'>Edit &lt;profile&gt;</a>"
+        );
+    }
+}
+
+#[cfg(test)]
+mod proptests {
+    use super::*;
+    use proptest::prelude::*;
+    use serde_json::{Map, Value as Json};
+
+    const DATE_STYLES: [&str; 5] = [
+        "PKDateStyleNone",
+        "PKDateStyleShort",
+        "PKDateStyleMedium",
+        "PKDateStyleLong",
+        "PKDateStyleFull",
+    ];
+    const NUMBER_STYLES: [&str; 4] = [
+        "PKNumberStyleDecimal",
+        "PKNumberStylePercent",
+        "PKNumberStyleScientific",
+        "PKNumberStyleSpellOut",
+    ];
+
+    fn value() -> impl Strategy<Value = Json> {
+        prop_oneof![
+            "[ -~]{0,16}".prop_map(Json::from),
+            any::<i32>().prop_map(Json::from),
+            (-1e9f64..1e9).prop_map(Json::from),
+        ]
+    }
+
+    /// pass.json field with any combination of date and number keys
+    fn field_json() -> impl Strategy<Value = Json> {
+        let date = (
+            prop::option::of(prop::sample::select(&DATE_STYLES[..])),
+            prop::option::of(any::<bool>()),
+            prop::option::of(any::<bool>()),
+            prop::option::of(prop::sample::select(&DATE_STYLES[..])),
+        );
+        let number = (
+            prop::option::of("[A-Z]{3}"),
+            prop::option::of(prop::sample::select(&NUMBER_STYLES[..])),
+        );
+        (
+            "[a-z-]{1,12}",
+            prop::option::of("[ -~]{0,16}"),
+            value(),
+            date,
+            number,
+        )
+            .prop_map(|(key, label, value, date, number)| {
+                let mut json = Map::new();
+                json.insert("key".into(), key.into());
+                json.insert("value".into(), value);
+                let (date_style, ignores_time_zone, is_relative, time_style) = date;
+                let (currency_code, number_style) = number;
+                let optional = vec![
+                    ("label", label.map(Json::from)),
+                    ("dateStyle", date_style.map(Json::from)),
+                    ("ignoresTimeZone", ignores_time_zone.map(Json::from)),
+                    ("isRelative", is_relative.map(Json::from)),
+                    ("timeStyle", time_style.map(Json::from)),
+                    ("currencyCode", currency_code.map(Json::from)),
+                    ("numberStyle", number_style.map(Json::from)),
+                ];
+                for (key, value) in optional {
+                    if let Some(value) = value {
+                        json.insert(key.into(), value);
+                    }
+                }
+                Json::Object(json)
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn json_round_trip(json in field_json()) {
+            let field: Field = serde_json::from_value(json.clone()).unwrap();
+            prop_assert_eq!(field.date.is_some(), json.get("dateStyle").is_some()
+                || json.get("ignoresTimeZone").is_some()
+                || json.get("isRelative").is_some()
+                || json.get("timeStyle").is_some());
+            prop_assert_eq!(field.number.is_some(), json.get("currencyCode").is_some()
+                || json.get("numberStyle").is_some());
+            prop_assert_eq!(serde_json::to_value(&field).unwrap(), json);
+        }
+
+        #[test]
+        fn builder_round_trip(amount in -1e9f64..1e9, code in "[A-Z]{3}", relative in any::<bool>()) {
+            let price = serde_json::to_value(FieldBuilder::currency("price", amount, code).finish()).unwrap();
+            let parsed: Field = serde_json::from_value(price.clone()).unwrap();
+            prop_assert!(parsed.date.is_none());
+            prop_assert_eq!(serde_json::to_value(&parsed).unwrap(), price);
+
+            let time = ::std::time::UNIX_EPOCH;
+            let date = if relative {
+                FieldBuilder::relative_date("date", time)
+            } else {
+                FieldBuilder::date_time("date", time, DateTimeStyle::Long, DateTimeStyle::None)
+            };
+            let date = serde_json::to_value(date.finish()).unwrap();
+            let parsed: Field = serde_json::from_value(date.clone()).unwrap();
+            prop_assert!(parsed.number.is_none());
+            prop_assert_eq!(serde_json::to_value(&parsed).unwrap(), date);
+        }
+    }
+}
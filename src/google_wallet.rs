@@ -0,0 +1,655 @@
+use field::Field;
+use openssl::base64;
+use openssl::hash::MessageDigest;
+use openssl::pkey::{PKey, Private};
+use openssl::sign::Signer as OpensslSigner;
+use pass::{BarcodeFormat, Pass, Style, TransitType};
+use serde_derive::Deserialize;
+use serde_json::{json, Map, Value as Json};
+use std::fmt;
+use std::fs;
+use std::path;
+use std::time::{SystemTime, UNIX_EPOCH};
+use util::rgb_components;
+use {PassError, PassResult};
+
+/// Prefix of the "Save to Google Wallet" link, followed by the signed JWT
+const SAVE_URL: &str = "https://pay.google.com/gp/v/save/";
+
+/// Google Wallet pass type the Apple style is converted to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GoogleWalletKind {
+    EventTicket,
+    Flight,
+    Transit,
+    Loyalty,
+    Offer,
+    Generic,
+}
+
+impl GoogleWalletKind {
+    fn of(style: &Style) -> GoogleWalletKind {
+        match style {
+            Style::EventTicket(_) => GoogleWalletKind::EventTicket,
+            Style::BoardingPass(structure) => match structure.transit_type() {
+                Some(TransitType::Air) => GoogleWalletKind::Flight,
+                _ => GoogleWalletKind::Transit,
+            },
+            Style::StoreCard(_) => GoogleWalletKind::Loyalty,
+            Style::Coupon(_) => GoogleWalletKind::Offer,
+            Style::Generic(_) => GoogleWalletKind::Generic,
+        }
+    }
+
+    /// Name prefix of the JWT payload keys, e.g. `eventTicket` for `eventTicketObjects`
+    fn payload_name(self) -> &'static str {
+        match self {
+            GoogleWalletKind::EventTicket => "eventTicket",
+            GoogleWalletKind::Flight => "flight",
+            GoogleWalletKind::Transit => "transit",
+            GoogleWalletKind::Loyalty => "loyalty",
+            GoogleWalletKind::Offer => "offer",
+            GoogleWalletKind::Generic => "generic",
+        }
+    }
+}
+
+/// Class and object of Google Wallet REST API made from the pass
+#[derive(Debug, Clone)]
+pub struct GoogleWalletPass {
+    pub kind: GoogleWalletKind,
+
+    /// Shared design, e.g. `EventTicketClass`
+    pub class: Json,
+
+    /// Pass of a single user, e.g. `EventTicketObject`
+    pub object: Json,
+}
+
+/// Flight data Google Wallet requires, Apple boarding passes keep it only in free-form fields
+#[derive(Debug, Clone, Default)]
+pub struct FlightDetails {
+    /// Two character IATA airline code, e.g. `LX`
+    pub carrier_iata_code: String,
+
+    /// Flight number without the airline code, e.g. `123`
+    pub flight_number: String,
+
+    pub passenger_name: String,
+
+    /// Booking reference, e.g. `ABC123`
+    pub confirmation_code: String,
+}
+
+/// Converter from Apple pass to Google Wallet class and object
+///
+/// Conversion is an approximation: Apple fields are mapped to text modules,
+/// fields specific to a Google pass type are filled from primary fields.
+#[derive(Debug, Clone)]
+pub struct GoogleWallet {
+    issuer_id: String,
+    logo_uri: Option<String>,
+    flight: Option<FlightDetails>,
+}
+
+impl GoogleWallet {
+    /// Issuer identifier from the Google Pay & Wallet console
+    pub fn new<S: Into<String>>(issuer_id: S) -> GoogleWallet {
+        GoogleWallet {
+            issuer_id: issuer_id.into(),
+            logo_uri: None,
+            flight: None,
+        }
+    }
+
+    /// Public URL of the logo, Google Wallet doesn't accept embedded images
+    pub fn logo_uri<S: Into<String>>(mut self, uri: S) -> GoogleWallet {
+        self.logo_uri = Some(uri.into());
+        self
+    }
+
+    /// Flight of the converted air boarding pass
+    pub fn flight(mut self, details: FlightDetails) -> GoogleWallet {
+        self.flight = Some(details);
+        self
+    }
+
+    /// Convert pass to class and object of its Google Wallet type.
+    /// Flights, transit tickets and loyalty cards require logo URI.
+    /// Flights and transit tickets require two primary fields with origin and destination,
+    /// flights also require flight details, departure date and airport codes as the values.
+    /// Loyalty account id is the barcode message, or the serial number without barcodes.
+    pub fn convert(&self, pass: &Pass) -> PassResult<GoogleWalletPass> {
+        let kind = GoogleWalletKind::of(&pass.style);
+        let logo = match kind {
+            GoogleWalletKind::Flight | GoogleWalletKind::Transit | GoogleWalletKind::Loyalty => {
+                Some(
+                    self.logo_uri
+                        .as_ref()
+                        .ok_or(PassError::InvalidGoogleWalletPass("logo URI is required"))?,
+                )
+            }
+            _ => self.logo_uri.as_ref(),
+        };
+        let structure = pass.style.structure();
+        let class_id = format!(
+            "{}.{}",
+            self.issuer_id,
+            resource_id(&pass.pass_type_identifier)
+        );
+        let primary = structure.primary_fields();
+        let title = primary
+            .first()
+            .map(|field| field.value.to_string())
+            .unwrap_or_else(|| pass.description.clone());
+        let background_color = pass
+            .visual
+            .as_ref()
+            .and_then(|visual| visual.background_color.as_ref())
+            .and_then(|color| hex_color(color));
+
+        let mut class = Map::new();
+        class.insert("id".into(), json!(class_id));
+        let mut object = Map::new();
+        object.insert(
+            "id".into(),
+            json!(format!(
+                "{}.{}",
+                self.issuer_id,
+                resource_id(&pass.serial_number)
+            )),
+        );
+        object.insert("classId".into(), json!(class_id));
+        object.insert(
+            "state".into(),
+            json!(if pass.voided { "EXPIRED" } else { "ACTIVE" }),
+        );
+
+        if kind != GoogleWalletKind::Generic {
+            class.insert("issuerName".into(), json!(pass.organization_name));
+            class.insert("reviewStatus".into(), json!("UNDER_REVIEW"));
+            if let Some(color) = &background_color {
+                class.insert("hexBackgroundColor".into(), json!(color));
+            }
+        }
+        match kind {
+            GoogleWalletKind::EventTicket => {
+                class.insert("eventName".into(), localized(&title));
+                if let Some(logo) = logo {
+                    class.insert("logo".into(), image(logo));
+                }
+            }
+            GoogleWalletKind::Flight => {
+                let flight = self
+                    .flight
+                    .as_ref()
+                    .ok_or(PassError::InvalidGoogleWalletPass(
+                        "flight details are required",
+                    ))?;
+                let (origin, destination) = match primary {
+                    [origin, destination] => (origin, destination),
+                    _ => {
+                        return Err(PassError::InvalidGoogleWalletPass(
+                            "flight requires origin and destination primary fields",
+                        ))
+                    }
+                };
+                let departure =
+                    pass.relevant_date
+                        .as_ref()
+                        .ok_or(PassError::InvalidGoogleWalletPass(
+                            "flight requires relevant date",
+                        ))?;
+                class.insert(
+                    "flightHeader".into(),
+                    json!({
+                        "carrier": {
+                            "carrierIataCode": flight.carrier_iata_code,
+                            "airlineName": localized(&pass.organization_name),
+                            "airlineLogo": logo.map(|logo| image(logo)),
+                        },
+                        "flightNumber": flight.flight_number,
+                    }),
+                );
+                class.insert(
+                    "origin".into(),
+                    json!({ "airportIataCode": origin.value.to_string() }),
+                );
+                class.insert(
+                    "destination".into(),
+                    json!({ "airportIataCode": destination.value.to_string() }),
+                );
+                class.insert(
+                    "localScheduledDepartureDateTime".into(),
+                    json!(local_date_time(departure)),
+                );
+                object.insert("passengerName".into(), json!(flight.passenger_name));
+                object.insert(
+                    "reservationInfo".into(),
+                    json!({ "confirmationCode": flight.confirmation_code }),
+                );
+            }
+            GoogleWalletKind::Transit => {
+                let transit_type = match structure.transit_type() {
+                    Some(TransitType::Bus) => "BUS",
+                    Some(TransitType::Train) => "RAIL",
+                    Some(TransitType::Boat) => "FERRY",
+                    _ => "OTHER",
+                };
+                class.insert("transitType".into(), json!(transit_type));
+                if let Some(logo) = logo {
+                    class.insert("logo".into(), image(logo));
+                }
+                let (origin, destination) = match primary {
+                    [origin, destination] => (origin, destination),
+                    _ => {
+                        return Err(PassError::InvalidGoogleWalletPass(
+                            "transit requires origin and destination primary fields",
+                        ))
+                    }
+                };
+                let mut leg = json!({
+                    "originName": localized(&origin.value.to_string()),
+                    "destinationName": localized(&destination.value.to_string()),
+                });
+                if let Some(date) = &pass.relevant_date {
+                    leg["departureDateTime"] = json!(date);
+                }
+                object.insert("tripType".into(), json!("ONE_WAY"));
+                object.insert("ticketLeg".into(), leg);
+            }
+            GoogleWalletKind::Loyalty => {
+                class.insert("programName".into(), json!(pass.description));
+                if let Some(logo) = logo {
+                    class.insert("programLogo".into(), image(logo));
+                }
+                let account = pass
+                    .barcodes()
+                    .first()
+                    .map_or(&pass.serial_number, |barcode| &barcode.message);
+                object.insert("accountId".into(), json!(account));
+            }
+            GoogleWalletKind::Offer => {
+                class.insert("title".into(), json!(title));
+                class.insert("provider".into(), json!(pass.organization_name));
+                class.insert("redemptionChannel".into(), json!("BOTH"));
+            }
+            GoogleWalletKind::Generic => {
+                object.insert("cardTitle".into(), localized(&pass.organization_name));
+                object.insert("header".into(), localized(&title));
+                if let Some(label) = primary.first().and_then(|field| field.label.as_ref()) {
+                    object.insert("subheader".into(), localized(label));
+                }
+                if let Some(color) = &background_color {
+                    object.insert("hexBackgroundColor".into(), json!(color));
+                }
+                if let Some(logo) = logo {
+                    object.insert("logo".into(), image(logo));
+                }
+            }
+        }
+
+        let text_modules: Vec<Json> = structure
+            .header_fields()
+            .iter()
+            .chain(structure.secondary_fields())
+            .chain(structure.auxiliary_fields())
+            .chain(structure.back_fields())
+            .map(text_module)
+            .collect();
+        if !text_modules.is_empty() {
+            object.insert("textModulesData".into(), json!(text_modules));
+        }
+
+        if let Some(barcode) = pass.barcodes().first() {
+            let barcode_type = match barcode.format {
+                BarcodeFormat::QR => "QR_CODE",
+                BarcodeFormat::PDF417 => "PDF_417",
+                BarcodeFormat::Aztec => "AZTEC",
+                BarcodeFormat::Code128 => "CODE_128",
+            };
+            let mut converted = json!({ "type": barcode_type, "value": barcode.message });
+            if let Some(alt_text) = &barcode.alt_text {
+                converted["alternateText"] = json!(alt_text);
+            }
+            object.insert("barcode".into(), converted);
+        }
+
+        let locations: Vec<Json> = pass
+            .locations
+            .iter()
+            .filter_map(|location| match (location.latitude, location.longitude) {
+                (Some(latitude), Some(longitude)) => {
+                    Some(json!({ "latitude": latitude, "longitude": longitude }))
+                }
+                _ => None,
+            })
+            .collect();
+        if !locations.is_empty() {
+            object.insert("locations".into(), json!(locations));
+        }
+
+        if let Some(expiration) = &pass.expiration_date {
+            object.insert(
+                "validTimeInterval".into(),
+                json!({ "end": { "date": expiration } }),
+            );
+        }
+
+        Ok(GoogleWalletPass {
+            kind,
+            class: Json::Object(class),
+            object: Json::Object(object),
+        })
+    }
+}
+
+/// Google Cloud service account key file, used to sign "Save to Google Wallet" links
+#[derive(Clone, Deserialize)]
+pub struct ServiceAccountKey {
+    pub client_email: String,
+
+    #[serde(default)]
+    pub private_key_id: Option<String>,
+
+    private_key: String,
+}
+
+impl ServiceAccountKey {
+    /// Read JSON key file downloaded from Google Cloud console
+    pub fn from_file<P: AsRef<path::Path>>(path: P) -> PassResult<ServiceAccountKey> {
+        let path = path.as_ref();
+        let content = fs::read(path).map_err(PassError::read(path))?;
+        serde_json::from_slice(&content).map_err(|source| PassError::Parse {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    /// Signed JWT with classes and objects for "Save to Google Wallet" link or button.
+    /// `origins` are domains of the pages the button is placed on.
+    pub fn save_jwt(&self, passes: &[GoogleWalletPass], origins: &[&str]) -> PassResult<String> {
+        let mut payload = Map::new();
+        for pass in passes {
+            for (suffix, value) in &[("Classes", &pass.class), ("Objects", &pass.object)] {
+                let key = format!("{}{}", pass.kind.payload_name(), suffix);
+                let entries = payload.entry(key).or_insert_with(|| json!([]));
+                if let Json::Array(entries) = entries {
+                    entries.push((*value).clone());
+                }
+            }
+        }
+
+        let issued_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+        let mut header = json!({ "alg": "RS256", "typ": "JWT" });
+        if let Some(key_id) = &self.private_key_id {
+            header["kid"] = json!(key_id);
+        }
+        let claims = json!({
+            "iss": self.client_email,
+            "aud": "google",
+            "typ": "savetowallet",
+            "iat": issued_at,
+            "origins": origins,
+            "payload": payload,
+        });
+
+        let signing_input = format!(
+            "{}.{}",
+            base64url(header.to_string().as_bytes()),
+            base64url(claims.to_string().as_bytes())
+        );
+        let signature = self.sign(signing_input.as_bytes())?;
+        Ok(format!("{}.{}", signing_input, base64url(&signature)))
+    }
+
+    /// "Save to Google Wallet" link
+    pub fn save_url(&self, passes: &[GoogleWalletPass], origins: &[&str]) -> PassResult<String> {
+        Ok(format!("{}{}", SAVE_URL, self.save_jwt(passes, origins)?))
+    }
+
+    fn sign(&self, data: &[u8]) -> PassResult<Vec<u8>> {
+        let private_key: PKey<Private> = PKey::private_key_from_pem(self.private_key.as_bytes())
+            .map_err(|source| PassError::Certificate { source })?;
+        OpensslSigner::new(MessageDigest::sha256(), &private_key)
+            .and_then(|mut signer| {
+                signer.update(data)?;
+                signer.sign_to_vec()
+            })
+            .map_err(|source| PassError::Sign { source })
+    }
+}
+
+impl fmt::Debug for ServiceAccountKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ServiceAccountKey")
+            .field("client_email", &self.client_email)
+            .field("private_key_id", &self.private_key_id)
+            .finish()
+    }
+}
+
+fn text_module(field: &Field) -> Json {
+    json!({
+        "id": resource_id(&field.key),
+        "header": field.label.clone().unwrap_or_default(),
+        "body": field.value.to_string(),
+    })
+}
+
+fn localized(value: &str) -> Json {
+    json!({ "defaultValue": { "language": "en-US", "value": value } })
+}
+
+fn image(uri: &str) -> Json {
+    json!({ "sourceUri": { "uri": uri } })
+}
+
+/// Google resource ids may contain only letters, digits, `.`, `_` and `-`
+fn resource_id(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '.' | '_' | '-' => c,
+            _ => '_',
+        })
+        .collect()
+}
+
+/// `2026-11-25T14:25-08:00` to `2026-11-25T14:25`, local time without the UTC offset
+fn local_date_time(date: &str) -> &str {
+    let time_start = date.find('T').map_or(date.len(), |index| index + 1);
+    match date[time_start..].find(&['+', '-', 'Z'][..]) {
+        Some(offset) => &date[..time_start + offset],
+        None => date,
+    }
+}
+
+/// `rgb(23, 187, 82)` to `#17bb52`
+fn hex_color(color: &str) -> Option<String> {
+    rgb_components(color).map(|[red, green, blue]| format!("#{:02x}{:02x}{:02x}", red, green, blue))
+}
+
+fn base64url(data: &[u8]) -> String {
+    base64::encode_block(data)
+        .trim_end_matches('=')
+        .replace('+', "-")
+        .replace('/', "_")
+}
+
+mod test {
+    #[test]
+    fn convert_store_card() {
+        use super::*;
+        use {Location, PassBuilder};
+
+        let pass = PassBuilder::new("0001 A", "pass.com.example", "CDHE9L6U22")
+            .organization_name("Coffee Shop")
+            .description("Rewards card")
+            .background_color("rgb(23, 187, 82)")
+            .add_primary_field(("balance", "BALANCE", "21 points"))
+            .add_back_field(("terms", "TERMS", "No cash value"))
+            .add_barcode((BarcodeFormat::QR, "0001"))
+            .add_location(Location::new(37.33, -122.03))
+            .finish_store_card();
+
+        let converted = GoogleWallet::new("3388000000012345678")
+            .logo_uri("https://example.com/logo.png")
+            .convert(&pass)
+            .unwrap();
+        assert_eq!(converted.kind, GoogleWalletKind::Loyalty);
+        assert_eq!(
+            converted.class["id"],
+            "3388000000012345678.pass.com.example"
+        );
+        assert_eq!(converted.class["hexBackgroundColor"], "#17bb52");
+        assert_eq!(hex_color("#17bb52"), None);
+        assert_eq!(hex_color("23, 187, 82"), None);
+        assert_eq!(converted.object["id"], "3388000000012345678.0001_A");
+        assert_eq!(converted.object["barcode"]["type"], "QR_CODE");
+        assert_eq!(converted.object["accountId"], "0001");
+        assert_eq!(
+            converted.object["textModulesData"][0]["body"],
+            "No cash value"
+        );
+        assert_eq!(converted.object["locations"][0]["longitude"], -122.03);
+
+        match GoogleWallet::new("3388000000012345678").convert(&pass) {
+            Err(PassError::InvalidGoogleWalletPass(reason)) => {
+                assert_eq!(reason, "logo URI is required")
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn convert_flight() {
+        use super::*;
+        use PassBuilder;
+
+        let builder = PassBuilder::new("0001", "pass.com.example", "CDHE9L6U22")
+            .organization_name("Skyport Airways")
+            .relevant_date("2026-11-25T14:25-08:00".into())
+            .add_primary_field(("depart", "SAN FRANCISCO", "SFO"));
+        let details = FlightDetails {
+            carrier_iata_code: "SK".into(),
+            flight_number: "815".into(),
+            passenger_name: "John Appleseed".into(),
+            confirmation_code: "ABC123".into(),
+        };
+        let wallet = GoogleWallet::new("3388000000012345678")
+            .logo_uri("https://example.com/logo.png")
+            .flight(details);
+
+        let one_way = builder.clone().finish_boarding_pass(TransitType::Air);
+        assert!(wallet.convert(&one_way).is_err());
+
+        let pass = builder
+            .add_primary_field(("arrive", "NEW YORK", "JFK"))
+            .finish_boarding_pass(TransitType::Air);
+        let converted = wallet.convert(&pass).unwrap();
+        assert_eq!(converted.kind, GoogleWalletKind::Flight);
+        let header = &converted.class["flightHeader"];
+        assert_eq!(header["flightNumber"], "815");
+        assert_eq!(header["carrier"]["carrierIataCode"], "SK");
+        assert_eq!(
+            header["carrier"]["airlineLogo"]["sourceUri"]["uri"],
+            "https://example.com/logo.png"
+        );
+        assert_eq!(converted.class["origin"]["airportIataCode"], "SFO");
+        assert_eq!(converted.class["destination"]["airportIataCode"], "JFK");
+        assert_eq!(
+            converted.class["localScheduledDepartureDateTime"],
+            "2026-11-25T14:25"
+        );
+        assert_eq!(converted.object["passengerName"], "John Appleseed");
+        assert_eq!(
+            converted.object["reservationInfo"]["confirmationCode"],
+            "ABC123"
+        );
+
+        let without_details = GoogleWallet::new("3388000000012345678")
+            .logo_uri("https://example.com/logo.png")
+            .convert(&pass);
+        assert!(without_details.is_err());
+    }
+
+    #[test]
+    fn convert_transit() {
+        use super::*;
+        use PassBuilder;
+
+        let builder = PassBuilder::new("0001", "pass.com.example", "CDHE9L6U22")
+            .organization_name("Coastal Rail")
+            .add_primary_field(("from", "FROM", "Oakland"));
+        let wallet =
+            GoogleWallet::new("3388000000012345678").logo_uri("https://example.com/logo.png");
+
+        match wallet.convert(&builder.clone().finish_boarding_pass(TransitType::Train)) {
+            Err(PassError::InvalidGoogleWalletPass(reason)) => assert_eq!(
+                reason,
+                "transit requires origin and destination primary fields"
+            ),
+            other => panic!("unexpected {:?}", other),
+        }
+
+        let pass = builder
+            .add_primary_field(("to", "TO", "Sacramento"))
+            .finish_boarding_pass(TransitType::Train);
+        let converted = wallet.convert(&pass).unwrap();
+        assert_eq!(converted.kind, GoogleWalletKind::Transit);
+        assert_eq!(converted.class["transitType"], "RAIL");
+        assert_eq!(converted.object["tripType"], "ONE_WAY");
+        assert_eq!(
+            converted.object["ticketLeg"]["destinationName"]["defaultValue"]["value"],
+            "Sacramento"
+        );
+    }
+
+    #[test]
+    fn signed_save_jwt() {
+        use super::*;
+        use openssl::rsa::Rsa;
+        use openssl::sign::Verifier;
+        use PassBuilder;
+
+        let private_key = PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap();
+        let key_file = json!({
+            "type": "service_account",
+            "client_email": "wallet@example.iam.gserviceaccount.com",
+            "private_key_id": "abc123",
+            "private_key": String::from_utf8(private_key.private_key_to_pem_pkcs8().unwrap()).unwrap(),
+        });
+        let key: ServiceAccountKey = serde_json::from_value(key_file).unwrap();
+
+        let pass = PassBuilder::new("0001", "pass.com.example", "CDHE9L6U22").finish_event_ticket();
+        let converted = GoogleWallet::new("3388000000012345678")
+            .convert(&pass)
+            .unwrap();
+        let jwt = key.save_jwt(&[converted], &["example.com"]).unwrap();
+
+        let parts: Vec<&str> = jwt.split('.').collect();
+        assert_eq!(parts.len(), 3);
+        let decode = |part: &str| {
+            let mut part = part.replace('-', "+").replace('_', "/");
+            let padding = (4 - part.len() % 4) % 4;
+            part.push_str(&"=="[..padding.min(2)]);
+            base64::decode_block(&part).unwrap()
+        };
+        let claims: Json = serde_json::from_slice(&decode(parts[1])).unwrap();
+        assert_eq!(claims["typ"], "savetowallet");
+        assert_eq!(
+            claims["payload"]["eventTicketObjects"][0]["id"],
+            "3388000000012345678.0001"
+        );
+
+        let mut verifier = Verifier::new(MessageDigest::sha256(), &private_key).unwrap();
+        verifier
+            .update(format!("{}.{}", parts[0], parts[1]).as_bytes())
+            .unwrap();
+        assert!(verifier.verify(&decode(parts[2])).unwrap());
+    }
+}
@@ -0,0 +1,216 @@
+use field::Field;
+use pass::{BarcodeFormat, FieldSection, Pass, Style};
+use std::fmt;
+
+// Characters fitting a row of the section with Wallet's default fonts, shared by its fields.
+// Rough numbers for a narrow iPhone screen, so a warning means text may be cut, not that it will.
+const HEADER_ROW: usize = 20;
+const PRIMARY_ROW: usize = 24;
+const BOARDING_PASS_PRIMARY_ROW: usize = 16;
+const DETAILS_ROW: usize = 36;
+
+/// Problem Wallet hides by truncating or dropping fields instead of rejecting the pass
+#[derive(Debug, Clone, PartialEq)]
+pub enum LayoutWarning {
+    /// Fields after `limit` are not displayed
+    TooManyFields {
+        section: FieldSection,
+        count: usize,
+        limit: usize,
+    },
+
+    /// Secondary and auxiliary fields share the space next to a square barcode
+    TooManyDetailFields { count: usize, limit: usize },
+
+    /// Value is longer than the space for the field
+    TextMayTruncate {
+        section: FieldSection,
+        key: String,
+        length: usize,
+        limit: usize,
+    },
+}
+
+impl fmt::Display for LayoutWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayoutWarning::TooManyFields {
+                section,
+                count,
+                limit,
+            } => write!(
+                f,
+                "{:?} section has {} fields, only {} are displayed",
+                section, count, limit
+            ),
+            LayoutWarning::TooManyDetailFields { count, limit } => write!(
+                f,
+                "{} secondary and auxiliary fields, only {} fit next to square barcode",
+                count, limit
+            ),
+            LayoutWarning::TextMayTruncate {
+                section,
+                key,
+                length,
+                limit,
+            } => write!(
+                f,
+                "Field {} in {:?} section has {} characters, may be truncated after {}",
+                key, section, length, limit
+            ),
+        }
+    }
+}
+
+impl Style {
+    /// Check field counts and text lengths against the space of the style.
+    /// Coupons, store cards and generic passes have less space with square barcode.
+    pub fn layout_warnings(&self, square_barcode: bool) -> Vec<LayoutWarning> {
+        let structure = self.structure();
+        let mut warnings = Vec::new();
+
+        for section in FieldSection::ALL.iter() {
+            let count = structure.fields(*section).len();
+            match self.field_limit(*section) {
+                Some(limit) if count > limit => warnings.push(LayoutWarning::TooManyFields {
+                    section: *section,
+                    count,
+                    limit,
+                }),
+                _ => {}
+            }
+        }
+
+        let shares_details = match self {
+            Style::Coupon(_) | Style::StoreCard(_) | Style::Generic(_) => square_barcode,
+            Style::BoardingPass(_) | Style::EventTicket(_) => false,
+        };
+        let details = structure.secondary_fields().len() + structure.auxiliary_fields().len();
+        if shares_details && details > 4 {
+            warnings.push(LayoutWarning::TooManyDetailFields {
+                count: details,
+                limit: 4,
+            });
+        }
+
+        for section in FieldSection::ALL.iter() {
+            let row = match self.row_width(*section) {
+                Some(row) => row,
+                None => continue,
+            };
+            let fields = structure.fields(*section);
+            let limit = row / fields.len().max(1);
+            for field in fields {
+                let length = value_length(field);
+                if length > limit {
+                    warnings.push(LayoutWarning::TextMayTruncate {
+                        section: *section,
+                        key: field.key.clone(),
+                        length,
+                        limit,
+                    });
+                }
+            }
+        }
+        warnings
+    }
+
+    fn field_limit(&self, section: FieldSection) -> Option<usize> {
+        let boarding_pass = matches!(self, Style::BoardingPass(_));
+        match section {
+            FieldSection::Header => Some(3),
+            FieldSection::Primary if boarding_pass => Some(2),
+            FieldSection::Primary => Some(1),
+            FieldSection::Secondary | FieldSection::Auxiliary if boarding_pass => Some(5),
+            FieldSection::Secondary | FieldSection::Auxiliary => Some(4),
+            FieldSection::Back => None,
+        }
+    }
+
+    /// Back fields are shown in a scrollable list and wrap
+    fn row_width(&self, section: FieldSection) -> Option<usize> {
+        match (section, self) {
+            (FieldSection::Header, _) => Some(HEADER_ROW),
+            (FieldSection::Primary, Style::BoardingPass(_)) => Some(BOARDING_PASS_PRIMARY_ROW),
+            (FieldSection::Primary, _) => Some(PRIMARY_ROW),
+            (FieldSection::Secondary, _) | (FieldSection::Auxiliary, _) => Some(DETAILS_ROW),
+            (FieldSection::Back, _) => None,
+        }
+    }
+}
+
+impl Pass {
+    /// Layout warnings for the style, taking into account the shape of the displayed barcode
+    pub fn layout_warnings(&self) -> Vec<LayoutWarning> {
+        let square_barcode = self.barcodes().first().is_some_and(|barcode| {
+            matches!(barcode.format, BarcodeFormat::QR | BarcodeFormat::Aztec)
+        });
+        self.style.layout_warnings(square_barcode)
+    }
+}
+
+/// Length of displayed value, formatted dates and numbers are not known before display
+fn value_length(field: &Field) -> usize {
+    if field.date.is_some() || field.number.is_some() {
+        0
+    } else {
+        field.value.to_string().chars().count()
+    }
+}
+
+mod test {
+    #[test]
+    fn over_capacity() {
+        use super::*;
+        use {BarcodeFormat, PassBuilder, TransitType};
+
+        let builder = PassBuilder::new("0001", "pass.com.example", "CDHE9L6U22")
+            .add_primary_field(("depart", "SAN FRANCISCO", "SFO"))
+            .add_primary_field(("arrive", "NEW YORK", "JFK"))
+            .add_auxiliary_field(("seat", "SEAT", "7A"))
+            .add_auxiliary_field(("class", "CLASS", "Economy"))
+            .add_auxiliary_field(("group", "GROUP", "3"))
+            .add_auxiliary_field(("gate", "GATE", "F12"));
+        let boarding_pass = builder
+            .clone()
+            .add_barcode((BarcodeFormat::QR, "0001"))
+            .finish_boarding_pass(TransitType::Air);
+        assert!(boarding_pass.layout_warnings().is_empty());
+
+        let mut coupon = builder
+            .clone()
+            .add_barcode((BarcodeFormat::QR, "0001"))
+            .finish_coupon();
+        let terms = (
+            "terms",
+            "TERMS",
+            "Valid at participating stores until October",
+        );
+        coupon.insert_field(FieldSection::Secondary, 0, terms);
+        assert_eq!(
+            coupon.layout_warnings(),
+            vec![
+                LayoutWarning::TooManyFields {
+                    section: FieldSection::Primary,
+                    count: 2,
+                    limit: 1,
+                },
+                LayoutWarning::TooManyDetailFields { count: 5, limit: 4 },
+                LayoutWarning::TextMayTruncate {
+                    section: FieldSection::Secondary,
+                    key: "terms".into(),
+                    length: 43,
+                    limit: 36,
+                },
+            ]
+        );
+
+        let wide_barcode = builder
+            .add_barcode((BarcodeFormat::PDF417, "0001"))
+            .finish_coupon();
+        assert!(!wide_barcode
+            .layout_warnings()
+            .iter()
+            .any(|warning| matches!(warning, LayoutWarning::TooManyDetailFields { .. })));
+    }
+}
@@ -1,70 +1,82 @@
 extern crate crypto;
 extern crate fs_extra;
+#[cfg(feature = "tokio")]
+extern crate futures;
+extern crate glob;
 extern crate keychain_services;
 extern crate openssl;
+#[cfg(feature = "render")]
+extern crate png;
+#[cfg(test)]
+extern crate proptest;
+#[cfg(feature = "render")]
+extern crate qrcode;
 extern crate serde;
 extern crate serde_derive;
 extern crate serde_json;
 extern crate tempdir;
+#[cfg(feature = "tokio")]
+extern crate tokio;
 extern crate zip;
 
+mod assets;
+mod attributed_value;
+mod barcode;
+mod diff;
+mod error;
 mod field;
+mod google_wallet;
+mod layout;
+#[cfg(feature = "tokio")]
+mod nonblocking;
 mod pass;
 mod personalization;
+mod preview;
+mod relevance;
+#[cfg(feature = "render")]
+mod render;
+mod sign;
+mod update;
 mod util;
+mod vas;
+mod walk;
+mod web_service;
 
 use crypto::{digest::Digest, sha1::Sha1};
-use std::collections::HashMap;
-use std::fmt;
+use serde::de::DeserializeOwned;
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
 use std::fs;
+use std::io;
 use std::io::prelude::*;
+use std::io::Cursor;
 use std::path;
+use std::sync::Arc;
 use tempdir::TempDir;
-
+use walk::source_files;
+use zip::write::{FileOptions, ZipWriter};
+
+pub use assets::*;
+pub use attributed_value::*;
+pub use barcode::validate_barcodes;
+pub use diff::*;
+pub use error::*;
 pub use field::*;
+pub use google_wallet::*;
+pub use layout::*;
+#[cfg(feature = "tokio")]
+pub use nonblocking::*;
 pub use pass::*;
 pub use personalization::*;
+pub use preview::Preview;
+pub use relevance::{MAX_BEACONS, MAX_LOCATIONS};
+pub use sign::*;
+pub use update::*;
+pub use vas::*;
+pub use walk::IgnoreRules;
+pub use web_service::*;
 
-// use Failure
-#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
-pub enum PassCreateError {
-    CantReadTempDir,
-    CantReadEntry(String),
-    CantParsePassFile(String),
-    PassContentNotFound,
-    CantCreateTempDir,
-    CantCopySourceToTemp,
-    CantSerializePass,
-    CantWritePassFile(String),
-    CantCalculateHashes,
-    CantCreateManifestFile,
-}
-
-impl fmt::Display for PassCreateError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        use PassCreateError::*;
-        let stringified = match self {
-            CantReadTempDir => "Can't read temporary directory".to_string(),
-            CantReadEntry(cause) => format!("Can't read {}", cause),
-            CantParsePassFile(cause) => format!("pass.json invalid: {}", cause),
-            PassContentNotFound => {
-                "Please, provide pass.json or instance of Pass with add_pass() method".to_string()
-            }
-            CantCreateTempDir => "Can't create temporary directory. Check rights".to_string(),
-            CantCopySourceToTemp => "Can't copy source files to temp directory".to_string(),
-            CantSerializePass => "Can't serialize pass.json".to_string(),
-            CantWritePassFile(cause) => format!("Can't write pass.json {}", cause),
-            CantCalculateHashes => "Can't calculate hashes for temp directory".to_string(),
-            CantCreateManifestFile => "Can't create manifest file at temp directory".to_string(),
-        };
-        write!(f, "PassCreateError: {}", stringified)
-    }
-}
-
-impl std::error::Error for PassCreateError {}
-
-type PassResult<T> = Result<T, PassCreateError>;
-type Manifest = HashMap<String, String>;
+type Manifest = BTreeMap<String, String>;
 
 /// Describes .pass directory with source files
 #[derive(Debug, Default)]
@@ -77,6 +89,21 @@ pub struct PassSource {
 
     /// content of the pass
     pass_content: Option<Pass>,
+
+    /// rewards enrollment, written as personalization.json
+    personalization: Option<Personalization>,
+
+    /// images with precalculated hashes, used instead of source directory
+    assets: Option<Arc<PreparedAssets>>,
+
+    /// identity to sign manifest.json, package is unsigned without it
+    signer: Option<Arc<Signer>>,
+
+    /// files of source directory to skip
+    ignore_rules: IgnoreRules,
+
+    /// produce byte-for-byte identical archives for the same input
+    reproducible: bool,
 }
 
 impl PassSource {
@@ -87,28 +114,88 @@ impl PassSource {
         }
     }
 
+    /// Use prepared assets instead of reading and hashing source directory on each build
+    pub fn with_assets(assets: Arc<PreparedAssets>) -> PassSource {
+        PassSource {
+            source_directory: assets.source_directory().to_string(),
+            assets: Some(assets),
+            ..Default::default()
+        }
+    }
+
     /// Add exists pass to source
     pub fn add_pass(&mut self, pass: Pass) -> &mut Self {
         self.pass_content = Some(pass);
         self
     }
 
-    /// Create .pkpass file in target directory
-    pub fn build_pkpass(&mut self) -> PassResult<()> {
+    /// Offer rewards enrollment for the pass, instead of personalization.json in source
+    pub fn add_personalization(&mut self, personalization: Personalization) -> &mut Self {
+        self.personalization = Some(personalization);
+        self
+    }
+
+    /// Sign manifest.json of the built packages
+    pub fn sign_with(&mut self, signer: Arc<Signer>) -> &mut Self {
+        self.signer = Some(signer);
+        self
+    }
+
+    /// Replace default rules to skip files of source directory
+    pub fn ignore_rules(&mut self, rules: IgnoreRules) -> &mut Self {
+        self.ignore_rules = rules;
+        self
+    }
+
+    /// Write archive entries with fixed timestamps, so the same input always produces
+    /// the same .pkpass. Use `Signer::without_signing_time` to make signature stable too.
+    pub fn reproducible(&mut self) -> &mut Self {
+        self.reproducible = true;
+        self
+    }
+
+    /// Create .pkpass archive and return its content
+    pub fn build_pkpass(&mut self) -> PassResult<Vec<u8>> {
         self.resolve_pass_content()?;
+        self.resolve_personalization()?;
+        self.check_pass_content()?;
         let tmp = Self::create_tmp_dir()?;
 
-        self.copy_source_files_to(tmp.path())?;
+        self.manifest = match &self.assets {
+            Some(assets) => {
+                assets.write_to(tmp.path())?;
+                assets.manifest().clone()
+            }
+            None => {
+                self.copy_source_files_to(tmp.path())?;
+                Manifest::new()
+            }
+        };
         self.write_pass_file_to(tmp.path())?;
+        self.write_personalization_to(tmp.path())?;
+        self.check_personalization_of(tmp.path())?;
         self.calculate_hashes_of(tmp.path())?;
         self.write_manifest_to(tmp.path())?;
-        Ok(())
+        self.write_signature_to(tmp.path())?;
+        self.package(tmp.path())
     }
 
     /// Parse pass.json from source directory if Pass not provided
     fn resolve_pass_content(&mut self) -> PassResult<()> {
-        if self.pass_content.is_none() && self.is_pass_file_exists_in_source() {
-            self.pass_content = Some(self.read_pass_file_from_source()?);
+        if self.pass_content.is_none() {
+            if !self.is_pass_file_exists_in_source() {
+                return Err(PassError::PassContentNotFound);
+            }
+            self.pass_content = Some(self.read_json_from_source("pass.json")?);
+        }
+        Ok(())
+    }
+
+    /// Parse personalization.json from source directory if Personalization not provided
+    fn resolve_personalization(&mut self) -> PassResult<()> {
+        let path = path::Path::new(&self.source_directory).join("personalization.json");
+        if self.personalization.is_none() && path.exists() {
+            self.personalization = Some(self.read_json_from_source("personalization.json")?);
         }
         Ok(())
     }
@@ -117,12 +204,10 @@ impl PassSource {
         self.pass_source_file_path().exists()
     }
 
-    fn read_pass_file_from_source(&self) -> PassResult<Pass> {
-        let content = read_file_to_vec(self.pass_source_file_path())
-            .map_err(|_| PassCreateError::CantReadEntry("pass.json".to_string()))?;
-        let pass: Pass = serde_json::from_slice(&content)
-            .map_err(|cause| PassCreateError::CantParsePassFile(cause.to_string()))?;
-        Ok(pass)
+    fn read_json_from_source<T: DeserializeOwned>(&self, file_name: &str) -> PassResult<T> {
+        let path = path::Path::new(&self.source_directory).join(file_name);
+        let content = read_file_to_vec(&path).map_err(PassError::read(&path))?;
+        serde_json::from_slice(&content).map_err(|source| PassError::Parse { path, source })
     }
 
     fn pass_source_file_path(&self) -> Box<path::Path> {
@@ -131,79 +216,127 @@ impl PassSource {
     }
 
     fn create_tmp_dir() -> PassResult<TempDir> {
-        TempDir::new("passsource").map_err(|_| PassCreateError::CantCreateTempDir)
+        TempDir::new("passsource").map_err(|source| PassError::CreateTempDir { source })
     }
 
     fn write_pass_file_to(&self, dir: &path::Path) -> PassResult<()> {
-        if !self.is_pass_file_exists_in_source() {
+        // prepared assets never contain pass.json, so it is written even if exists in source
+        if self.assets.is_some() || !self.is_pass_file_exists_in_source() {
             if let Some(pass) = &self.pass_content {
-                let serialized = serde_json::to_string_pretty(&pass)
-                    .map_err(|_| PassCreateError::CantSerializePass)?;
-
                 let pass_file_path = dir.join("pass.json");
-                fs::write(pass_file_path, serialized)
-                    .map_err(|err| PassCreateError::CantWritePassFile(err.to_string()))?;
+                let serialized =
+                    serde_json::to_string_pretty(&pass).map_err(|source| PassError::Serialize {
+                        path: pass_file_path.clone(),
+                        source,
+                    })?;
+
+                fs::write(&pass_file_path, serialized).map_err(PassError::write(pass_file_path))?;
             }
         }
         Ok(())
     }
 
-    fn copy_source_files_to(&mut self, dir: &path::Path) -> PassResult<()> {
-        fn walk(from: &path::Path, to: &path::Path) -> std::io::Result<()> {
-            for entry in fs::read_dir(&from)? {
-                // println!("{:?}", entry?);
-                let entry = entry?;
-                let entry_path = &entry.path();
-                let target = entry_path
-                    .strip_prefix(&from)
-                    .map_err(|__| std::io::Error::from(std::io::ErrorKind::Other))?;
-                fs::copy(entry.path(), to.join(target))?;
-            }
+    fn write_personalization_to(&self, dir: &path::Path) -> PassResult<()> {
+        if let Some(personalization) = &self.personalization {
+            let file_path = dir.join("personalization.json");
+            let serialized = serde_json::to_string_pretty(personalization).map_err(|source| {
+                PassError::Serialize {
+                    path: file_path.clone(),
+                    source,
+                }
+            })?;
 
-            Ok(())
+            fs::write(&file_path, serialized).map_err(PassError::write(file_path))?;
         }
+        Ok(())
+    }
 
-        walk(&path::Path::new(&self.source_directory), dir)
-            .map_err(|_| PassCreateError::CantCopySourceToTemp)?;
+    fn check_personalization_of(&self, dir: &path::Path) -> PassResult<()> {
+        match (&self.personalization, &self.pass_content) {
+            (Some(personalization), Some(pass)) => {
+                personalization.check(pass, dir.join(PERSONALIZATION_LOGO).exists())
+            }
+            _ => Ok(()),
+        }
+    }
 
+    /// Check NFC encryption key and barcodes before they land in pass.json
+    fn check_pass_content(&self) -> PassResult<()> {
+        if let Some(pass) = &self.pass_content {
+            if let Some(nfc) = &pass.nfc {
+                nfc.validate()?;
+            }
+            validate_barcodes(pass.barcodes())?;
+        }
         Ok(())
     }
 
-    fn calculate_hashes_of(&mut self, dir: &path::Path) -> PassResult<()> {
-        fn enumerate(dir: &path::Path) -> std::io::Result<Manifest> {
-            let mut manifest = Manifest::new();
+    fn copy_source_files_to(&mut self, dir: &path::Path) -> PassResult<()> {
+        let source = path::Path::new(&self.source_directory);
+        for file in source_files(source, &self.ignore_rules)? {
+            let target = dir.join(&file.name);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent).map_err(PassError::write(parent))?;
+            }
+            let mut content = fs::File::open(&file.path).map_err(PassError::read(file.path))?;
+            fs::File::create(&target)
+                .and_then(|mut copy| io::copy(&mut content, &mut copy))
+                .map_err(PassError::write(target))?;
+        }
 
-            for entry in fs::read_dir(dir)? {
-                let entry = entry?;
-                if entry.metadata()?.is_file() {
-                    let file_name = format!("{:?}", entry.file_name());
-                    let content = read_file_to_vec(entry.path())?;
-                    let hash = get_hash(&content);
+        Ok(())
+    }
 
-                    manifest.insert(file_name, hash);
-                }
+    /// Hash files of the directory that are not hashed yet, e.g. by prepared assets
+    fn calculate_hashes_of(&mut self, dir: &path::Path) -> PassResult<()> {
+        for file in source_files(dir, &IgnoreRules::none())? {
+            if let Entry::Vacant(entry) = self.manifest.entry(file.name) {
+                let content = read_file_to_vec(&file.path).map_err(PassError::read(file.path))?;
+                entry.insert(get_hash(&content));
             }
-
-            Ok(manifest)
         }
 
-        self.manifest = enumerate(&dir).map_err(|_| PassCreateError::CantCalculateHashes)?;
         Ok(())
     }
 
     fn write_manifest_to(&self, dir: &path::Path) -> PassResult<()> {
-        fn produce(target: &path::Path, manifest: &Manifest) -> std::io::Result<()> {
-            let file_path = target.join("manifest.json");
-            let content = serde_json::to_vec_pretty(&manifest)
-                .map_err(|_| std::io::Error::from(std::io::ErrorKind::Other))?;
-            fs::write(file_path, content)?;
-            Ok(())
+        let file_path = dir.join("manifest.json");
+        let content =
+            serde_json::to_vec_pretty(&self.manifest).map_err(|source| PassError::Serialize {
+                path: file_path.clone(),
+                source,
+            })?;
+        fs::write(&file_path, content).map_err(PassError::write(file_path))
+    }
+
+    fn write_signature_to(&self, dir: &path::Path) -> PassResult<()> {
+        if let Some(signer) = &self.signer {
+            let manifest_path = dir.join("manifest.json");
+            let manifest =
+                read_file_to_vec(&manifest_path).map_err(PassError::read(manifest_path))?;
+            let signature = signer.sign(&manifest)?;
+            let signature_path = dir.join("signature");
+            fs::write(&signature_path, signature).map_err(PassError::write(signature_path))?;
         }
+        Ok(())
+    }
 
-        produce(dir, &self.manifest).map_err(|_| PassCreateError::CantCreateManifestFile)
+    fn package(&self, dir: &path::Path) -> PassResult<Vec<u8>> {
+        let files = read_files_of(dir)?;
+        zip_files(&files, self.reproducible).map_err(|source| PassError::Package { source })
     }
 }
 
+/// Read names and contents of all files in the directory and its subdirectories
+fn read_files_of(dir: &path::Path) -> PassResult<Vec<(String, Vec<u8>)>> {
+    let mut files = Vec::new();
+    for file in source_files(dir, &IgnoreRules::none())? {
+        let content = read_file_to_vec(&file.path).map_err(PassError::read(file.path))?;
+        files.push((file.name, content));
+    }
+    Ok(files)
+}
+
 fn read_file_to_vec<P: AsRef<std::path::Path>>(path: P) -> std::io::Result<Vec<u8>> {
     let mut file = fs::File::open(path.as_ref())?;
     let length = file.metadata()?.len();
@@ -216,9 +349,138 @@ fn read_file_to_vec<P: AsRef<std::path::Path>>(path: P) -> std::io::Result<Vec<u
     Ok(buffer)
 }
 
+/// Pack files into .pkpass archive, sorted by name.
+/// Reproducible archive has all timestamps set to 1980-01-01.
+fn zip_files<'a, I>(files: I, reproducible: bool) -> zip::result::ZipResult<Vec<u8>>
+where
+    I: IntoIterator<Item = &'a (String, Vec<u8>)>,
+{
+    let mut files: Vec<_> = files.into_iter().collect();
+    files.sort_by(|(left, _), (right, _)| left.cmp(right));
+
+    let mut options = FileOptions::default();
+    if reproducible {
+        options = options.last_modified_time(zip::DateTime::default());
+    }
+
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    for (file_name, content) in files {
+        zip.start_file(file_name.as_str(), options)?;
+        zip.write_all(content)?;
+    }
+    Ok(zip.finish()?.into_inner())
+}
+
 #[inline]
 fn get_hash(content: &[u8]) -> String {
     let mut hasher = Sha1::new();
     hasher.input(content);
     hasher.result_str()
 }
+
+mod test {
+    #[test]
+    fn build_pkpass_from_assets() {
+        use super::*;
+
+        let source = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/BoardingPass.pass");
+        let assets = Arc::new(PreparedAssets::prepare(source).unwrap());
+        let pass = PassBuilder::new("0001", "pass.com.example", "CDHE9L6U22")
+            .organization_name("Skyport Airways")
+            .description("Skyport Boarding Pass")
+            .finish_boarding_pass(TransitType::Air);
+
+        let mut source = PassSource::with_assets(assets);
+        source.add_pass(pass);
+        let pkpass = source.build_pkpass().unwrap();
+
+        let mut archive = zip::ZipArchive::new(Cursor::new(pkpass)).unwrap();
+        let mut manifest = String::new();
+        archive
+            .by_name("manifest.json")
+            .unwrap()
+            .read_to_string(&mut manifest)
+            .unwrap();
+        let manifest: Manifest = serde_json::from_str(&manifest).unwrap();
+
+        assert_eq!(manifest.len(), 5);
+        assert!(manifest.contains_key("pass.json"));
+        assert!(archive.by_name("signature").is_err());
+    }
+
+    #[test]
+    fn reproducible_pkpass() {
+        use super::*;
+
+        let source = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/BoardingPass.pass");
+        let assets = Arc::new(PreparedAssets::prepare(source).unwrap());
+        let build = || {
+            let pass = PassBuilder::new("0001", "pass.com.example", "CDHE9L6U22")
+                .organization_name("Skyport Airways")
+                .description("Skyport Boarding Pass")
+                .add_user_info("passenger", "John Appleseed")
+                .add_user_info("seat", "12A")
+                .add_user_info("class", "Coach")
+                .finish_boarding_pass(TransitType::Air);
+
+            let mut source = PassSource::with_assets(assets.clone());
+            source.add_pass(pass).reproducible();
+            source.build_pkpass().unwrap()
+        };
+
+        let pkpass = build();
+        assert_eq!(pkpass, build());
+
+        let mut archive = zip::ZipArchive::new(Cursor::new(pkpass)).unwrap();
+        assert!(archive.len() > 0);
+        for index in 0..archive.len() {
+            let time = archive.by_index(index).unwrap().last_modified();
+            let fields = (time.year(), time.month(), time.day());
+            assert_eq!(fields, (1980, 1, 1));
+            assert_eq!((time.hour(), time.minute(), time.second()), (0, 0, 0));
+        }
+    }
+
+    #[test]
+    fn build_pkpass_with_personalization() {
+        use super::*;
+
+        let example =
+            path::Path::new(env!("CARGO_MANIFEST_DIR")).join("examples/BoardingPass.pass");
+        let source = TempDir::new("personalization").unwrap();
+        fs::copy(example.join("icon.png"), source.path().join("icon.png")).unwrap();
+        fs::copy(
+            example.join("logo.png"),
+            source.path().join(PERSONALIZATION_LOGO),
+        )
+        .unwrap();
+
+        let pass = PassBuilder::new("0001", "pass.com.example", "CDHE9L6U22")
+            .organization_name("Coffee Shop")
+            .description("Rewards card")
+            .nfc("0001", None)
+            .finish_store_card();
+        let personalization = Personalization {
+            required_personalization_fields: vec![PersonalizationField::Name],
+            description: "Sign up and earn points.".to_string(),
+            terms_and_conditions: None,
+        };
+
+        let mut pass_source = PassSource::new(source.path().to_string_lossy());
+        pass_source
+            .add_pass(pass)
+            .add_personalization(personalization);
+        let pkpass = pass_source.build_pkpass().unwrap();
+
+        let mut archive = zip::ZipArchive::new(Cursor::new(pkpass)).unwrap();
+        let mut manifest = String::new();
+        archive
+            .by_name("manifest.json")
+            .unwrap()
+            .read_to_string(&mut manifest)
+            .unwrap();
+        let manifest: Manifest = serde_json::from_str(&manifest).unwrap();
+        assert!(manifest.contains_key("personalization.json"));
+        assert!(manifest.contains_key(PERSONALIZATION_LOGO));
+    }
+}
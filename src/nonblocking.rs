@@ -0,0 +1,227 @@
+use futures::future::{self, Either, FutureExt, TryFutureExt};
+use serde::de::DeserializeOwned;
+use std::future::Future;
+use std::path;
+use std::sync::Arc;
+use tokio::fs;
+use tokio::task;
+use walk::{source_files, SourceFile};
+use {
+    get_hash, validate_barcodes, zip_files, IgnoreRules, Pass, PassError, PassResult, PassSource,
+    Personalization, PreparedAssets, Signer, PERSONALIZATION_LOGO,
+};
+
+type Files = Vec<(String, Vec<u8>)>;
+
+impl PassSource {
+    /// Non-blocking variant of `build_pkpass`.
+    /// Files are read with tokio, hashing, signing and packing are moved to the blocking pool.
+    pub fn build_pkpass_async(&self) -> impl Future<Output = PassResult<Vec<u8>>> + Send + 'static {
+        let source = path::PathBuf::from(&self.source_directory);
+        let assets = self.assets.clone();
+        let signer = self.signer.clone();
+        let reproducible = self.reproducible;
+
+        let files = match assets {
+            // prepared assets are already in memory
+            Some(_) => Either::Left(future::ok(Files::new())),
+            None => Either::Right(read_source_files_async(
+                source.clone(),
+                self.ignore_rules.clone(),
+            )),
+        };
+
+        future::try_join3(
+            resolve_pass_content_async(&source, self.pass_content.clone()),
+            resolve_personalization_async(&source, self.personalization.clone()),
+            files,
+        )
+        .and_then(move |(pass, personalization, files)| {
+            task::spawn_blocking(move || {
+                package_files(
+                    assets.as_deref(),
+                    files,
+                    &pass,
+                    personalization.as_ref(),
+                    signer.as_deref(),
+                    reproducible,
+                )
+            })
+            .map(|joined| joined.unwrap_or_else(|source| Err(PassError::Task { source })))
+        })
+    }
+}
+
+impl Signer {
+    /// Sign manifest.json content on the blocking pool
+    pub fn sign_async(
+        self: Arc<Self>,
+        manifest: Vec<u8>,
+    ) -> impl Future<Output = PassResult<Vec<u8>>> {
+        task::spawn_blocking(move || self.sign(&manifest))
+            .map(|joined| joined.unwrap_or_else(|source| Err(PassError::Task { source })))
+    }
+}
+
+/// Read and parse pass.json without blocking
+pub fn read_pass_async<P: AsRef<path::Path>>(path: P) -> impl Future<Output = PassResult<Pass>> {
+    read_json_async(path.as_ref().to_path_buf())
+}
+
+fn read_json_async<T: DeserializeOwned>(
+    path: path::PathBuf,
+) -> impl Future<Output = PassResult<T>> {
+    fs::read(path.clone())
+        .map_err(PassError::read(path.clone()))
+        .and_then(move |content| {
+            future::ready(
+                serde_json::from_slice(&content)
+                    .map_err(|source| PassError::Parse { path, source }),
+            )
+        })
+}
+
+/// Parse pass.json from source directory if Pass not provided
+fn resolve_pass_content_async(
+    source: &path::Path,
+    pass_content: Option<Pass>,
+) -> impl Future<Output = PassResult<Pass>> {
+    if let Some(pass) = pass_content {
+        return Either::Left(future::ok(pass));
+    }
+
+    let pass_file = source.join("pass.json");
+    Either::Right(
+        fs::metadata(pass_file.clone()).then(move |metadata| match metadata {
+            Ok(_) => Either::Left(read_json_async(pass_file)),
+            Err(_) => Either::Right(future::err(PassError::PassContentNotFound)),
+        }),
+    )
+}
+
+/// Parse personalization.json from source directory if Personalization not provided
+fn resolve_personalization_async(
+    source: &path::Path,
+    personalization: Option<Personalization>,
+) -> impl Future<Output = PassResult<Option<Personalization>>> {
+    if personalization.is_some() {
+        return Either::Left(future::ok(personalization));
+    }
+
+    let personalization_file = source.join("personalization.json");
+    Either::Right(
+        fs::metadata(personalization_file.clone()).then(move |metadata| match metadata {
+            Ok(_) => Either::Left(read_json_async(personalization_file).map_ok(Some)),
+            Err(_) => Either::Right(future::ok(None)),
+        }),
+    )
+}
+
+/// Walking directory tree is moved to the blocking pool, files are read with tokio
+fn read_source_files_async(
+    source: path::PathBuf,
+    rules: IgnoreRules,
+) -> impl Future<Output = PassResult<Files>> {
+    fn read_file(file: SourceFile) -> impl Future<Output = PassResult<(String, Vec<u8>)>> {
+        let SourceFile { name, path } = file;
+        fs::read(path.clone())
+            .map_ok(move |content| (name, content))
+            .map_err(PassError::read(path))
+    }
+
+    task::spawn_blocking(move || source_files(&source, &rules))
+        .map(|joined| joined.unwrap_or_else(|source| Err(PassError::Task { source })))
+        .and_then(|files| future::try_join_all(files.into_iter().map(read_file)))
+}
+
+/// Same steps as `build_pkpass` does in temporary directory, but in memory
+fn package_files(
+    assets: Option<&PreparedAssets>,
+    mut files: Files,
+    pass: &Pass,
+    personalization: Option<&Personalization>,
+    signer: Option<&Signer>,
+    reproducible: bool,
+) -> PassResult<Vec<u8>> {
+    let mut manifest = assets
+        .map(|assets| assets.manifest().clone())
+        .unwrap_or_default();
+
+    if let Some(nfc) = &pass.nfc {
+        nfc.validate()?;
+    }
+    validate_barcodes(pass.barcodes())?;
+
+    let has_pass_file = files.iter().any(|(file_name, _)| file_name == "pass.json");
+    if !has_pass_file {
+        let serialized =
+            serde_json::to_vec_pretty(pass).map_err(|source| PassError::Serialize {
+                path: "pass.json".into(),
+                source,
+            })?;
+        files.push(("pass.json".to_string(), serialized));
+    }
+
+    let asset_files = assets.map(PreparedAssets::files).unwrap_or(&[]);
+    if let Some(personalization) = personalization {
+        let has_logo = asset_files
+            .iter()
+            .chain(files.iter())
+            .any(|(file_name, _)| file_name == PERSONALIZATION_LOGO);
+        personalization.check(pass, has_logo)?;
+
+        let serialized =
+            serde_json::to_vec_pretty(personalization).map_err(|source| PassError::Serialize {
+                path: "personalization.json".into(),
+                source,
+            })?;
+        files.retain(|(file_name, _)| file_name != "personalization.json");
+        files.push(("personalization.json".to_string(), serialized));
+    }
+
+    for (file_name, content) in &files {
+        if !manifest.contains_key(file_name) {
+            manifest.insert(file_name.clone(), get_hash(content));
+        }
+    }
+
+    let manifest = serde_json::to_vec_pretty(&manifest).map_err(|source| PassError::Serialize {
+        path: "manifest.json".into(),
+        source,
+    })?;
+    if let Some(signer) = signer {
+        let signature = signer.sign(&manifest)?;
+        files.push(("signature".to_string(), signature));
+    }
+    files.push(("manifest.json".to_string(), manifest));
+
+    zip_files(asset_files.iter().chain(files.iter()), reproducible)
+        .map_err(|source| PassError::Package { source })
+}
+
+mod test {
+    #[test]
+    fn build_from_assets_async() {
+        use super::*;
+        use std::io::Cursor;
+        use {PassBuilder, TransitType};
+
+        let source = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/BoardingPass.pass");
+        let assets = Arc::new(PreparedAssets::prepare(source).unwrap());
+        let pass = PassBuilder::new("0001", "pass.com.example", "CDHE9L6U22")
+            .organization_name("Skyport Airways")
+            .description("Skyport Boarding Pass")
+            .finish_boarding_pass(TransitType::Air);
+
+        let mut source = PassSource::with_assets(assets);
+        source.add_pass(pass);
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let pkpass = runtime.block_on(source.build_pkpass_async()).unwrap();
+
+        let archive = zip::ZipArchive::new(Cursor::new(pkpass)).unwrap();
+        assert_eq!(archive.len(), 6);
+    }
+}
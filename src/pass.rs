@@ -1,7 +1,10 @@
 use field::Field;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_derive::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::time::SystemTime;
 use util::*;
+use {validate_attributed_value, PassError, PassResult};
 
 /// The top level of the pass.json file is a dictionary.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -39,6 +42,7 @@ pub struct Pass {
     pub associated_store_identifiers: Vec<i32>,
 
     #[serde(default)]
+    #[serde(serialize_with = "serialize_sorted")]
     pub user_info: HashMap<String, String>,
 
     /// Date and time when the pass expires.
@@ -96,7 +100,109 @@ pub struct Pass {
     pub nfc: Option<NFC>,
 }
 
+impl Pass {
+    pub fn structure(&self) -> &Structure {
+        self.style.structure()
+    }
+
+    pub fn structure_mut(&mut self) -> &mut Structure {
+        self.style.structure_mut()
+    }
+
+    /// Field with the key in any section
+    pub fn field(&self, key: &str) -> Option<&Field> {
+        self.structure().field(key)
+    }
+
+    /// Field with the key in any section, to change it in place
+    pub fn field_mut(&mut self, key: &str) -> Option<&mut Field> {
+        self.structure_mut().field_mut(key)
+    }
+
+    /// Insert field at the index of the section, or at the end if the index is out of range
+    pub fn insert_field<T: Into<Field>>(&mut self, section: FieldSection, index: usize, field: T) {
+        self.structure_mut().insert_field(section, index, field)
+    }
+
+    pub fn remove_field(&mut self, key: &str) -> Option<Field> {
+        self.structure_mut().remove_field(key)
+    }
+
+    /// Move field with the key to the index of the section, counted without the moved field.
+    /// Returns `false` if there is no such field.
+    pub fn move_field(&mut self, key: &str, section: FieldSection, index: usize) -> bool {
+        self.structure_mut().move_field(key, section, index)
+    }
+
+    /// Check required keys, keys allowed only for some styles and barcode fallbacks
+    pub fn validate(&self) -> PassResult<()> {
+        let mut violations = Vec::new();
+        let required = [
+            ("serialNumber", &self.serial_number),
+            ("passTypeIdentifier", &self.pass_type_identifier),
+            ("teamIdentifier", &self.team_identifier),
+            ("organizationName", &self.organization_name),
+            ("description", &self.description),
+        ];
+        for (key, value) in required.iter() {
+            if value.is_empty() {
+                violations.push(format!("missing {}", key));
+            }
+        }
+
+        let boarding_pass = matches!(self.style, Style::BoardingPass(_));
+        let event_ticket = matches!(self.style, Style::EventTicket(_));
+        if boarding_pass && self.structure().transit_type.is_none() {
+            violations.push("missing transitType".to_string());
+        }
+        if !boarding_pass && self.structure().transit_type.is_some() {
+            violations.push("transitType is allowed only for boarding passes".to_string());
+        }
+        let grouping = self
+            .visual
+            .as_ref()
+            .is_some_and(|visual| visual.grouping_identifier.is_some());
+        if grouping && !boarding_pass && !event_ticket {
+            violations.push(
+                "groupingIdentifier is allowed only for boarding passes and event tickets"
+                    .to_string(),
+            );
+        }
+
+        violations.extend(self.barcode_violations());
+        violations.extend(self.relevance_violations());
+
+        if let Some(web_service) = &self.web_service {
+            if web_service.authentication_token.len() < 16 {
+                violations.push("authenticationToken must be 16 characters or longer".to_string());
+            }
+        }
+
+        let mut keys = Vec::new();
+        for section in FieldSection::ALL.iter() {
+            for field in self.structure().fields(*section) {
+                if keys.contains(&field.key.as_str()) {
+                    violations.push(format!("field key {} is not unique", field.key));
+                }
+                keys.push(&field.key);
+                if let Some(value) = &field.attributed_value {
+                    if let Err(error) = validate_attributed_value(value) {
+                        violations.push(format!("field {}: {}", field.key, error));
+                    }
+                }
+            }
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(PassError::InvalidPass(violations))
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[serde(remote = "Self")]
 #[serde(rename_all = "camelCase")]
 #[serde(default)]
 pub struct VisualAppearance {
@@ -107,6 +213,12 @@ pub struct VisualAppearance {
     #[serde(default)]
     pub barcodes: Vec<Barcode>,
 
+    /// Barcode for iOS 8 and earlier, which don't read `barcodes`.
+    /// On parsing it's added to empty `barcodes`, use `set_legacy_barcode()` to emit it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub barcode: Option<Barcode>,
+
     /// Background color of the pass, specified as an CSS-style RGB triple.
     /// For example, rgb(23, 187, 82).
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -148,6 +260,22 @@ pub struct VisualAppearance {
     pub suppress_strip_shine: bool,
 }
 
+impl Serialize for VisualAppearance {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        VisualAppearance::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for VisualAppearance {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut visual = VisualAppearance::deserialize(deserializer)?;
+        if visual.barcodes.is_empty() {
+            visual.barcodes.extend(visual.barcode.clone());
+        }
+        Ok(visual)
+    }
+}
+
 /// Information about a location beacon.
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[serde(rename_all = "camelCase")]
@@ -180,11 +308,11 @@ pub struct Location {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub altitude: Option<f64>,
 
-    /// Latitude, in meters, of the location.
+    /// Latitude, in degrees, of the location.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub latitude: Option<f64>,
 
-    /// Longitude, in meters, of the location.
+    /// Longitude, in degrees, of the location.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub longitude: Option<f64>,
 
@@ -194,29 +322,6 @@ pub struct Location {
     pub relevant_text: Option<String>,
 }
 
-/// From<(altitude, latitude)>
-impl From<(f64, f64)> for Location {
-    fn from((alt, lat): (f64, f64)) -> Location {
-        Location {
-            altitude: Some(alt),
-            latitude: Some(lat),
-            ..Default::default()
-        }
-    }
-}
-
-/// From<(altitude, latitude, longitude)>
-impl From<(f64, f64, f64)> for Location {
-    fn from((alt, lat, lon): (f64, f64, f64)) -> Location {
-        Location {
-            altitude: Some(alt),
-            latitude: Some(lat),
-            longitude: Some(lon),
-            ..Default::default()
-        }
-    }
-}
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum Style {
@@ -233,6 +338,49 @@ impl Default for Style {
     }
 }
 
+impl Style {
+    /// Fields of the pass, whatever the style is
+    pub fn structure(&self) -> &Structure {
+        match self {
+            Style::BoardingPass(structure)
+            | Style::Coupon(structure)
+            | Style::EventTicket(structure)
+            | Style::Generic(structure)
+            | Style::StoreCard(structure) => structure,
+        }
+    }
+
+    pub fn structure_mut(&mut self) -> &mut Structure {
+        match self {
+            Style::BoardingPass(structure)
+            | Style::Coupon(structure)
+            | Style::EventTicket(structure)
+            | Style::Generic(structure)
+            | Style::StoreCard(structure) => structure,
+        }
+    }
+}
+
+/// Part of the pass where a field is displayed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldSection {
+    Header,
+    Primary,
+    Secondary,
+    Auxiliary,
+    Back,
+}
+
+impl FieldSection {
+    pub(crate) const ALL: [FieldSection; 5] = [
+        FieldSection::Header,
+        FieldSection::Primary,
+        FieldSection::Secondary,
+        FieldSection::Auxiliary,
+        FieldSection::Back,
+    ];
+}
+
 /// Keys that define the structure of the pass.
 /// These keys are used for all pass styles and partition the fields into the various parts of the pass.
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
@@ -265,6 +413,123 @@ pub struct Structure {
     transit_type: Option<TransitType>,
 }
 
+impl Structure {
+    pub fn header_fields(&self) -> &[Field] {
+        &self.header_fields
+    }
+
+    pub fn primary_fields(&self) -> &[Field] {
+        &self.primary_fields
+    }
+
+    pub fn secondary_fields(&self) -> &[Field] {
+        &self.secondary_fields
+    }
+
+    pub fn auxiliary_fields(&self) -> &[Field] {
+        &self.auxiliary_fields
+    }
+
+    pub fn back_fields(&self) -> &[Field] {
+        &self.back_fields
+    }
+
+    pub fn transit_type(&self) -> Option<&TransitType> {
+        self.transit_type.as_ref()
+    }
+
+    pub fn header_fields_mut(&mut self) -> &mut Vec<Field> {
+        &mut self.header_fields
+    }
+
+    pub fn primary_fields_mut(&mut self) -> &mut Vec<Field> {
+        &mut self.primary_fields
+    }
+
+    pub fn secondary_fields_mut(&mut self) -> &mut Vec<Field> {
+        &mut self.secondary_fields
+    }
+
+    pub fn auxiliary_fields_mut(&mut self) -> &mut Vec<Field> {
+        &mut self.auxiliary_fields
+    }
+
+    pub fn back_fields_mut(&mut self) -> &mut Vec<Field> {
+        &mut self.back_fields
+    }
+
+    pub fn set_transit_type(&mut self, transit_type: Option<TransitType>) {
+        self.transit_type = transit_type;
+    }
+
+    pub fn fields(&self, section: FieldSection) -> &[Field] {
+        match section {
+            FieldSection::Header => &self.header_fields,
+            FieldSection::Primary => &self.primary_fields,
+            FieldSection::Secondary => &self.secondary_fields,
+            FieldSection::Auxiliary => &self.auxiliary_fields,
+            FieldSection::Back => &self.back_fields,
+        }
+    }
+
+    pub fn fields_mut(&mut self, section: FieldSection) -> &mut Vec<Field> {
+        match section {
+            FieldSection::Header => &mut self.header_fields,
+            FieldSection::Primary => &mut self.primary_fields,
+            FieldSection::Secondary => &mut self.secondary_fields,
+            FieldSection::Auxiliary => &mut self.auxiliary_fields,
+            FieldSection::Back => &mut self.back_fields,
+        }
+    }
+
+    /// Section and index of the field with the key
+    pub fn position(&self, key: &str) -> Option<(FieldSection, usize)> {
+        FieldSection::ALL.iter().find_map(|section| {
+            self.fields(*section)
+                .iter()
+                .position(|field| field.key == key)
+                .map(|index| (*section, index))
+        })
+    }
+
+    /// Field with the key in any section
+    pub fn field(&self, key: &str) -> Option<&Field> {
+        self.position(key)
+            .map(|(section, index)| &self.fields(section)[index])
+    }
+
+    pub fn field_mut(&mut self, key: &str) -> Option<&mut Field> {
+        match self.position(key) {
+            Some((section, index)) => Some(&mut self.fields_mut(section)[index]),
+            None => None,
+        }
+    }
+
+    /// Insert field at the index of the section, or at the end if the index is out of range
+    pub fn insert_field<T: Into<Field>>(&mut self, section: FieldSection, index: usize, field: T) {
+        let fields = self.fields_mut(section);
+        let index = index.min(fields.len());
+        fields.insert(index, field.into());
+    }
+
+    pub fn remove_field(&mut self, key: &str) -> Option<Field> {
+        self.position(key)
+            .map(|(section, index)| self.fields_mut(section).remove(index))
+    }
+
+    /// Move field with the key to the index of the section, counted without the moved field.
+    /// Returns `false` if there is no such field.
+    pub fn move_field(&mut self, key: &str, section: FieldSection, index: usize) -> bool {
+        match self.remove_field(key) {
+            Some(field) => {
+                self.insert_field(section, index, field);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum TransitType {
     /// PKTransitTypeAir
@@ -396,6 +661,7 @@ pub struct PassBuilder {
     max_distance: Option<u32>,
     relevant_date: Option<String>,
     visual: VisualAppearance,
+    legacy_barcode: bool,
     web_service: Option<WebService>,
     nfc: Option<NFC>,
 }
@@ -419,6 +685,12 @@ impl PassBuilder {
         }
     }
 
+    /// Serial number for the pass built from another one
+    pub fn serial_number<S: Into<String>>(mut self, serial_number: S) -> PassBuilder {
+        self.serial_number = serial_number.into();
+        self
+    }
+
     pub fn organization_name<O: Into<String>>(mut self, organization_name: O) -> PassBuilder {
         self.organization_name = Some(organization_name.into());
         self
@@ -468,6 +740,7 @@ impl PassBuilder {
         self
     }
 
+    /// Only reduces the distance Wallet uses for the style, see `Style::relevant_distance()`
     pub fn max_distance(mut self, distance: u32) -> PassBuilder {
         self.max_distance = Some(distance);
         self
@@ -478,6 +751,11 @@ impl PassBuilder {
         self
     }
 
+    pub fn relevant_time(mut self, time: SystemTime) -> PassBuilder {
+        self.relevant_date = Some(w3c_date(time));
+        self
+    }
+
     pub fn add_auxiliary_field<T: Into<Field>>(mut self, field: T) -> PassBuilder {
         self.structure.auxiliary_fields.push(field.into());
         self
@@ -508,6 +786,12 @@ impl PassBuilder {
         self
     }
 
+    /// Also emit first non-Code128 barcode as `barcode` for iOS 8 and earlier
+    pub fn legacy_barcode(mut self) -> PassBuilder {
+        self.legacy_barcode = true;
+        self
+    }
+
     pub fn background_color<C: Into<String>>(mut self, color: C) -> PassBuilder {
         self.visual.background_color = Some(color.into());
         self
@@ -558,7 +842,10 @@ impl PassBuilder {
         self
     }
 
-    fn build(self, style: Style) -> Pass {
+    fn build(mut self, style: Style) -> Pass {
+        if self.legacy_barcode {
+            self.visual.set_legacy_barcode();
+        }
         Pass {
             format_version: 1,
             serial_number: self.serial_number,
@@ -582,6 +869,13 @@ impl PassBuilder {
         }
     }
 
+    /// Transit type of a boarding pass the builder was made from is not allowed in other styles
+    fn structure_without_transit_type(&self) -> Structure {
+        let mut structure = self.structure.clone();
+        structure.transit_type = None;
+        structure
+    }
+
     pub fn finish_boarding_pass(self, transit_type: TransitType) -> Pass {
         let mut structure = self.structure.clone();
         structure.transit_type = Some(transit_type);
@@ -589,24 +883,85 @@ impl PassBuilder {
     }
 
     pub fn finish_coupon(self) -> Pass {
-        let structure = self.structure.clone();
+        let structure = self.structure_without_transit_type();
         self.build(Style::Coupon(structure))
     }
 
     pub fn finish_event_ticket(self) -> Pass {
-        let structure = self.structure.clone();
+        let structure = self.structure_without_transit_type();
         self.build(Style::EventTicket(structure))
     }
 
     pub fn finish_generic(self) -> Pass {
-        let structure = self.structure.clone();
+        let structure = self.structure_without_transit_type();
         self.build(Style::Generic(structure))
     }
 
     pub fn finish_store_card(self) -> Pass {
-        let structure = self.structure.clone();
+        let structure = self.structure_without_transit_type();
         self.build(Style::StoreCard(structure))
     }
+
+    /// Same as `finish_boarding_pass`, but fails with `PassError::InvalidPass`
+    /// listing missing required keys and keys not allowed for the style
+    pub fn try_finish_boarding_pass(self, transit_type: TransitType) -> PassResult<Pass> {
+        checked(self.finish_boarding_pass(transit_type))
+    }
+
+    pub fn try_finish_coupon(self) -> PassResult<Pass> {
+        checked(self.finish_coupon())
+    }
+
+    pub fn try_finish_event_ticket(self) -> PassResult<Pass> {
+        checked(self.finish_event_ticket())
+    }
+
+    pub fn try_finish_generic(self) -> PassResult<Pass> {
+        checked(self.finish_generic())
+    }
+
+    pub fn try_finish_store_card(self) -> PassResult<Pass> {
+        checked(self.finish_store_card())
+    }
+}
+
+fn checked(pass: Pass) -> PassResult<Pass> {
+    pass.validate().map(|_| pass)
+}
+
+impl From<Pass> for PassBuilder {
+    /// Start from an existing pass, e.g. a base design loaded from pass.json.
+    /// The legacy barcode is kept if the pass has it.
+    fn from(pass: Pass) -> PassBuilder {
+        let visual = pass.visual.unwrap_or_default();
+        PassBuilder {
+            serial_number: pass.serial_number,
+            pass_type_identifier: pass.pass_type_identifier,
+            team_identifier: pass.team_identifier,
+            organization_name: Some(pass.organization_name),
+            description: Some(pass.description),
+            structure: pass.style.structure().clone(),
+            app_launch_url: pass.app_launch_url,
+            associated_store_identifiers: pass.associated_store_identifiers,
+            user_info: pass.user_info,
+            expiration_date: pass.expiration_date,
+            voided: pass.voided,
+            beacons: pass.beacons,
+            locations: pass.locations,
+            max_distance: pass.max_distance,
+            relevant_date: pass.relevant_date,
+            legacy_barcode: visual.barcode.is_some(),
+            visual,
+            web_service: pass.web_service,
+            nfc: pass.nfc,
+        }
+    }
+}
+
+impl Pass {
+    pub fn to_builder(&self) -> PassBuilder {
+        PassBuilder::from(self.clone())
+    }
 }
 
 pub fn rgb(r: u8, g: u8, b: u8) -> String {
@@ -623,7 +978,7 @@ mod test {
                 "vxwxd7J8AlNNFPS8k0a0FfUFtq0ewzFdc",
                 "https://example.com/passes/",
             ).relevant_date("2012-07-22T14:25-08:00".into())
-            .add_location((-122.3748889, 37.6189722))
+            .add_location(Location::new(37.6189722, -122.3748889))
             .add_barcode((
                 BarcodeFormat::PDF417,
                 "SFOJFK JOHN APPLESEED LH451 2012-07-22T14:25-08:00",
@@ -759,5 +1114,152 @@ mod test {
         let pass: Pass = serde_json::from_str(&src).unwrap();
 
         println!("{:#?}", pass);
+
+        match pass.barcodes() {
+            [barcode] => assert!(barcode.message.starts_with("SFOJFK")),
+            other => panic!("legacy barcode not parsed: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn legacy_barcode() {
+        use super::*;
+
+        let pass = PassBuilder::new("0001", "pass.com.example", "CDHE9L6U22")
+            .add_barcode((BarcodeFormat::Code128, "0001"))
+            .add_barcode((BarcodeFormat::QR, "0001"))
+            .legacy_barcode()
+            .finish_generic();
+        let json = serde_json::to_value(&pass).unwrap();
+        assert_eq!(json["barcode"]["format"], "PKBarcodeFormatQR");
+        assert_eq!(json["barcodes"].as_array().unwrap().len(), 2);
+
+        let parsed: Pass = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.barcodes().len(), 2);
+    }
+
+    #[test]
+    fn edit_fields() {
+        use super::*;
+
+        let mut pass = PassBuilder::new("0001", "pass.com.example", "CDHE9L6U22")
+            .add_header_field(("gate", "GATE", "23"))
+            .add_primary_field(("depart", "SAN FRANCISCO", "SFO"))
+            .add_primary_field(("arrive", "NEW YORK", "JFK"))
+            .finish_boarding_pass(TransitType::Air);
+
+        pass.field_mut("gate").unwrap().value = "F12".into();
+        assert_eq!(pass.field("gate").unwrap().value.to_string(), "F12");
+        assert!(pass.field_mut("seat").is_none());
+
+        pass.insert_field(FieldSection::Auxiliary, 10, ("seat", "SEAT", "7A"));
+        assert_eq!(
+            pass.structure().position("seat"),
+            Some((FieldSection::Auxiliary, 0))
+        );
+
+        assert!(pass.move_field("arrive", FieldSection::Primary, 0));
+        assert!(pass.move_field("gate", FieldSection::Back, 0));
+        let keys: Vec<&str> = pass
+            .structure()
+            .primary_fields()
+            .iter()
+            .map(|field| field.key.as_str())
+            .collect();
+        assert_eq!(keys, vec!["arrive", "depart"]);
+        assert!(pass.structure().header_fields().is_empty());
+        assert_eq!(pass.structure().back_fields()[0].key, "gate");
+        assert!(!pass.move_field("seat2", FieldSection::Header, 0));
+
+        assert_eq!(pass.remove_field("seat").unwrap().key, "seat");
+        assert!(pass.remove_field("seat").is_none());
+        assert!(pass.structure().transit_type().is_some());
+    }
+
+    #[test]
+    fn builder_from_pass() {
+        use super::*;
+
+        let design = PassBuilder::new("0001", "pass.com.example", "CDHE9L6U22")
+            .organization_name("Skyport Airways")
+            .add_primary_field(("depart", "SAN FRANCISCO", "SFO"))
+            .add_back_field(("terms", "TERMS", "Non refundable"))
+            .add_barcode((BarcodeFormat::QR, "0001"))
+            .legacy_barcode()
+            .finish_boarding_pass(TransitType::Air);
+        let src = serde_json::to_string(&design).unwrap();
+        let base: Pass = serde_json::from_str(&src).unwrap();
+
+        let pass = base
+            .to_builder()
+            .serial_number("0002")
+            .add_back_field(("seat", "SEAT", "7A"))
+            .finish_boarding_pass(TransitType::Train);
+
+        assert_eq!(pass.serial_number, "0002");
+        assert_eq!(pass.organization_name, base.organization_name);
+        assert_eq!(pass.barcodes().len(), base.barcodes().len());
+        assert!(pass.visual.as_ref().unwrap().barcode.is_some());
+        assert_eq!(
+            pass.structure().primary_fields().len(),
+            base.structure().primary_fields().len()
+        );
+        assert_eq!(
+            pass.structure().back_fields().len(),
+            base.structure().back_fields().len() + 1
+        );
+        match pass.structure().transit_type() {
+            Some(TransitType::Train) => {}
+            other => panic!("unexpected {:?}", other),
+        }
+        let generic = base.to_builder().finish_generic();
+        assert!(generic.structure().transit_type().is_none());
+    }
+
+    #[test]
+    fn try_finish() {
+        use super::*;
+
+        let builder = PassBuilder::new("0001", "pass.com.example", "CDHE9L6U22")
+            .add_header_field(("gate", "GATE", "23"));
+        let mut terms = Field::from(("terms", "TERMS", "Non refundable"));
+        terms.attributed_value = Some("<b>Non refundable</b>".into());
+        let duplicate_key = builder
+            .clone()
+            .add_back_field(("gate", "GATE", "23"))
+            .add_back_field(terms);
+        match duplicate_key.try_finish_boarding_pass(TransitType::Air) {
+            Err(PassError::InvalidPass(violations)) => assert_eq!(
+                violations,
+                vec![
+                    "missing organizationName",
+                    "missing description",
+                    "field key gate is not unique",
+                    "field terms: Invalid attributed value: only <a> tag is supported",
+                ]
+            ),
+            other => panic!("unexpected {:?}", other),
+        }
+
+        let boarding_pass = builder
+            .organization_name("Skyport Airways")
+            .description("Boarding pass")
+            .try_finish_boarding_pass(TransitType::Air)
+            .unwrap();
+        assert!(boarding_pass.validate().is_ok());
+        let mut coupon = boarding_pass.to_builder().try_finish_coupon().unwrap();
+        assert!(coupon.structure().transit_type().is_none());
+        assert!(coupon.validate().is_ok());
+
+        coupon
+            .structure_mut()
+            .set_transit_type(Some(TransitType::Bus));
+        match coupon.validate() {
+            Err(PassError::InvalidPass(violations)) => assert_eq!(
+                violations,
+                vec!["transitType is allowed only for boarding passes"]
+            ),
+            other => panic!("unexpected {:?}", other),
+        }
     }
 }
@@ -1,6 +1,12 @@
+use pass::{Pass, Style};
 use serde_derive::{Deserialize, Serialize};
+use {PassError, PassResult};
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Image shown on the rewards enrollment sheet, required for personalizable passes
+pub const PERSONALIZATION_LOGO: &str = "personalizationLogo.png";
+
+/// Content of personalization.json, which turns a store card into rewards enrollment offer
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Personalization {
     pub required_personalization_fields: Vec<PersonalizationField>,
@@ -11,7 +17,7 @@ pub struct Personalization {
     pub terms_and_conditions: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PersonalizationField {
     /// PKPassPersonalizationFieldName
     #[serde(rename = "PKPassPersonalizationFieldName")]
@@ -30,6 +36,112 @@ pub enum PersonalizationField {
     PhoneNumber,
 }
 
+impl PersonalizationField {
+    /// Every field identifier supported by Wallet
+    pub const ALL: [PersonalizationField; 4] = [
+        PersonalizationField::Name,
+        PersonalizationField::PostalCode,
+        PersonalizationField::EmailAddress,
+        PersonalizationField::PhoneNumber,
+    ];
+}
+
+/// Values user entered on the enrollment sheet, posted by Wallet to the personalize endpoint
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(default)]
+pub struct PersonalizationInfo {
+    /// Name as entered by user, provided for `PersonalizationField::Name`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub full_name: Option<String>,
+
+    /// Given name component of the full name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub given_name: Option<String>,
+
+    /// Family name component of the full name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub family_name: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email_address: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phone_number: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub postal_code: Option<String>,
+
+    /// Two-letter ISO 3166-1 country code, sent along with postal code
+    #[serde(rename = "ISOCountryCode")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iso_country_code: Option<String>,
+}
+
+impl PersonalizationInfo {
+    /// Full name, composed of given and family names when Wallet sends only components
+    pub fn full_name(&self) -> Option<String> {
+        let non_empty = |value: &Option<String>| value.clone().filter(|value| !value.is_empty());
+        non_empty(&self.full_name).or_else(|| {
+            match (non_empty(&self.given_name), non_empty(&self.family_name)) {
+                (Some(given), Some(family)) => Some(format!("{} {}", given, family)),
+                (given, family) => given.or(family),
+            }
+        })
+    }
+
+    /// Submitted value of the requested field
+    pub fn value(&self, field: PersonalizationField) -> Option<String> {
+        let value = match field {
+            PersonalizationField::Name => return self.full_name(),
+            PersonalizationField::PostalCode => &self.postal_code,
+            PersonalizationField::EmailAddress => &self.email_address,
+            PersonalizationField::PhoneNumber => &self.phone_number,
+        };
+        value.clone().filter(|value| !value.is_empty())
+    }
+}
+
+impl Personalization {
+    /// Check that every required field has a value
+    pub fn check_info(&self, info: &PersonalizationInfo) -> PassResult<()> {
+        for field in &self.required_personalization_fields {
+            if info.value(*field).is_none() {
+                return Err(PassError::MissingPersonalizationField(*field));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check requirements of Wallet for personalizable passes
+    pub(crate) fn check(&self, pass: &Pass, has_logo: bool) -> PassResult<()> {
+        if self.required_personalization_fields.is_empty() {
+            return Err(PassError::InvalidPersonalization(
+                "at least one personalization field is required",
+            ));
+        }
+        match pass.style {
+            Style::StoreCard(_) => {}
+            _ => {
+                return Err(PassError::InvalidPersonalization(
+                    "only store cards can be personalized",
+                ))
+            }
+        }
+        if pass.nfc.is_none() {
+            return Err(PassError::InvalidPersonalization(
+                "pass must contain nfc dictionary",
+            ));
+        }
+        if !has_logo {
+            return Err(PassError::InvalidPersonalization(
+                "personalizationLogo.png is missing",
+            ));
+        }
+        Ok(())
+    }
+}
+
 mod test {
 
     #[test]
@@ -50,4 +162,86 @@ mod test {
         println!("{}", json);
         println!("{:#?}", pers);
     }
+
+    #[test]
+    fn field_names_round_trip() {
+        use super::*;
+
+        let names: Vec<String> = PersonalizationField::ALL
+            .iter()
+            .map(|field| serde_json::to_string(field).unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "\"PKPassPersonalizationFieldName\"",
+                "\"PKPassPersonalizationFieldPostalCode\"",
+                "\"PKPassPersonalizationFieldEmailAddress\"",
+                "\"PKPassPersonalizationFieldPhoneNumber\"",
+            ]
+        );
+        for (field, name) in PersonalizationField::ALL.iter().zip(&names) {
+            assert_eq!(
+                serde_json::from_str::<PersonalizationField>(name).unwrap(),
+                *field
+            );
+        }
+    }
+
+    #[test]
+    fn info_round_trip() {
+        use super::*;
+
+        let json = r#"{
+            "givenName": "John",
+            "familyName": "Appleseed",
+            "emailAddress": "john@example.com",
+            "phoneNumber": "+1 555 0100",
+            "postalCode": "95014",
+            "ISOCountryCode": "US"
+        }"#;
+        let info: PersonalizationInfo = serde_json::from_str(json).unwrap();
+        assert_eq!(info.iso_country_code.as_ref().unwrap(), "US");
+        assert_eq!(
+            info.value(PersonalizationField::Name).unwrap(),
+            "John Appleseed"
+        );
+        assert_eq!(
+            info.value(PersonalizationField::PostalCode).unwrap(),
+            "95014"
+        );
+
+        let serialized = serde_json::to_value(&info).unwrap();
+        assert_eq!(serialized["ISOCountryCode"], "US");
+        assert!(serialized.get("fullName").is_none());
+        assert_eq!(
+            serde_json::from_value::<PersonalizationInfo>(serialized).unwrap(),
+            info
+        );
+    }
+
+    #[test]
+    fn check_requirements() {
+        use super::*;
+        use PassBuilder;
+
+        let pers = Personalization {
+            required_personalization_fields: vec![PersonalizationField::EmailAddress],
+            description: "Enter your information to sign up and earn points.".to_string(),
+            terms_and_conditions: None,
+        };
+        let builder = PassBuilder::new("0001", "pass.com.example", "CDHE9L6U22")
+            .organization_name("Coffee Shop")
+            .description("Rewards card");
+
+        let generic = builder.clone().finish_generic();
+        assert!(pers.check(&generic, true).is_err());
+
+        let without_nfc = builder.clone().finish_store_card();
+        assert!(pers.check(&without_nfc, true).is_err());
+
+        let store_card = builder.nfc("0001", None).finish_store_card();
+        assert!(pers.check(&store_card, false).is_err());
+        assert!(pers.check(&store_card, true).is_ok());
+    }
 }
@@ -0,0 +1,400 @@
+use field::{Field, TextAlignment};
+use openssl::base64;
+use pass::{Pass, Style, TransitType};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use util::{escape_html, rgb_components};
+use {sanitize_attributed_value, PreparedAssets};
+
+/// Images of a pass the preview knows how to place
+const IMAGES: [&str; 6] = ["background", "footer", "icon", "logo", "strip", "thumbnail"];
+
+/// Width of the pass on the screen, in CSS pixels
+const WIDTH: u32 = 320;
+
+/// Approximation of the Wallet appearance of a pass, for review in a browser
+#[derive(Debug, Clone)]
+pub struct Preview<'a> {
+    pass: &'a Pass,
+
+    /// image name without extension, e.g. `logo`, with PNG content
+    images: BTreeMap<String, Vec<u8>>,
+}
+
+impl<'a> Preview<'a> {
+    pub fn new(pass: &'a Pass) -> Preview<'a> {
+        Preview {
+            pass,
+            images: BTreeMap::new(),
+        }
+    }
+
+    /// Use image for `logo`, `icon`, `strip`, `thumbnail`, `background` or `footer`
+    pub fn image<N: Into<String>>(mut self, name: N, png: Vec<u8>) -> Preview<'a> {
+        self.images.insert(name.into(), png);
+        self
+    }
+
+    /// Take images from prepared assets, preferring @2x versions
+    pub fn with_assets(mut self, assets: &PreparedAssets) -> Preview<'a> {
+        for name in IMAGES.iter() {
+            let retina = format!("{}@2x.png", name);
+            let regular = format!("{}.png", name);
+            let found = assets
+                .files()
+                .iter()
+                .find(|(file_name, _)| *file_name == retina)
+                .or_else(|| {
+                    assets
+                        .files()
+                        .iter()
+                        .find(|(file_name, _)| *file_name == regular)
+                });
+            if let Some((_, content)) = found {
+                self.images.insert(name.to_string(), content.clone());
+            }
+        }
+        self
+    }
+
+    /// Standalone HTML page with front and back of the pass
+    pub fn to_html(&self) -> String {
+        format!(
+            "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"/><title>{}</title><style>{}</style></head><body>{}</body></html>\n",
+            escape_html(&self.pass.description),
+            self.css(),
+            self.body()
+        )
+    }
+
+    /// Standalone SVG with the same markup as HTML embedded into foreignObject
+    pub fn to_svg(&self) -> String {
+        let height = self.estimated_height();
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\"><foreignObject width=\"100%\" height=\"100%\"><div xmlns=\"http://www.w3.org/1999/xhtml\"><style>{css}</style>{body}</div></foreignObject></svg>\n",
+            width = WIDTH + 40,
+            height = height,
+            css = self.css(),
+            body = self.body()
+        )
+    }
+
+    /// Colors are written into the style sheet only in `rgb(r, g, b)` format,
+    /// anything else falls back to the default
+    fn css(&self) -> String {
+        let visual = self.pass.visual.as_ref();
+        let color = |color: Option<&String>, default: &str| {
+            color
+                .and_then(|color| rgb_components(color))
+                .map_or(default.to_string(), |[red, green, blue]| {
+                    format!("rgb({}, {}, {})", red, green, blue)
+                })
+        };
+        let background = color(
+            visual.and_then(|visual| visual.background_color.as_ref()),
+            "rgb(255, 255, 255)",
+        );
+        let foreground = color(
+            visual.and_then(|visual| visual.foreground_color.as_ref()),
+            "rgb(0, 0, 0)",
+        );
+        let label = color(
+            visual.and_then(|visual| visual.label_color.as_ref()),
+            &foreground,
+        );
+
+        format!(
+            "body{{margin:0;padding:20px;background:#eee;font-family:-apple-system,Helvetica,sans-serif}}\
+.pass{{width:{width}px;margin:0 0 20px;border-radius:12px;overflow:hidden;background:{background};color:{foreground};background-size:cover}}\
+.row{{display:flex;justify-content:space-between;padding:6px 12px}}\
+.field{{flex:1;min-width:0;overflow:hidden;text-overflow:ellipsis;white-space:nowrap}}\
+.label{{font-size:10px;text-transform:uppercase;color:{label}}}\
+.value{{font-size:15px}}\
+.primary .value{{font-size:28px}}\
+.header{{align-items:center}}\
+.header img{{max-height:40px;max-width:140px}}\
+.logo-text{{flex:1;font-size:16px;padding-left:8px}}\
+.strip{{background-size:cover;background-position:center}}\
+.thumbnail{{max-width:80px;max-height:80px}}\
+.transit{{font-size:24px;align-self:center;padding:0 8px}}\
+.barcode{{margin:12px;padding:8px;background:#fff;color:#000;text-align:center;border-radius:6px;font-size:12px}}\
+.barcode svg{{max-width:100%;max-height:140px}}\
+.back .field{{white-space:normal;padding:6px 0;border-bottom:1px solid rgba(127,127,127,.3)}}",
+            width = WIDTH,
+            background = background,
+            foreground = foreground,
+            label = label
+        )
+    }
+
+    fn body(&self) -> String {
+        let structure = self.pass.style.structure();
+        let mut html = String::new();
+
+        let background = match self.pass.style {
+            Style::EventTicket(_) if !self.images.contains_key("strip") => {
+                self.images.get("background")
+            }
+            _ => None,
+        };
+        match background {
+            Some(png) => {
+                let _ = write!(
+                    html,
+                    "<div class=\"pass front\" style=\"background-image:url({})\">",
+                    data_uri(png)
+                );
+            }
+            None => html.push_str("<div class=\"pass front\">"),
+        }
+
+        // header with logo, logo text and header fields
+        html.push_str("<div class=\"row header\">");
+        if let Some(png) = self.images.get("logo") {
+            let _ = write!(html, "<img src=\"{}\" alt=\"logo\"/>", data_uri(png));
+        }
+        let logo_text = self
+            .pass
+            .visual
+            .as_ref()
+            .and_then(|visual| visual.logo_text.as_ref());
+        let _ = write!(
+            html,
+            "<div class=\"logo-text\">{}</div>",
+            logo_text.map(|text| escape_html(text)).unwrap_or_default()
+        );
+        for field in structure.header_fields() {
+            html.push_str(&field_html(field));
+        }
+        html.push_str("</div>");
+
+        // primary fields are laid out differently by every style
+        let strip = match self.pass.style {
+            Style::Coupon(_) | Style::StoreCard(_) | Style::EventTicket(_) => {
+                self.images.get("strip")
+            }
+            _ => None,
+        };
+        match strip {
+            Some(png) => {
+                let _ = write!(
+                    html,
+                    "<div class=\"row primary strip\" style=\"background-image:url({})\">",
+                    data_uri(png)
+                );
+            }
+            None => html.push_str("<div class=\"row primary\">"),
+        }
+        match &self.pass.style {
+            Style::BoardingPass(_) => {
+                let mut primary = structure.primary_fields().iter();
+                if let Some(origin) = primary.next() {
+                    html.push_str(&field_html(origin));
+                }
+                let _ = write!(
+                    html,
+                    "<div class=\"transit\">{}</div>",
+                    transit_symbol(structure.transit_type())
+                );
+                for field in primary {
+                    html.push_str(&field_html(field));
+                }
+            }
+            _ => {
+                for field in structure.primary_fields() {
+                    html.push_str(&field_html(field));
+                }
+            }
+        }
+        let has_thumbnail = match self.pass.style {
+            Style::Generic(_) => strip.is_none(),
+            Style::EventTicket(_) => strip.is_none(),
+            _ => false,
+        };
+        if has_thumbnail {
+            if let Some(png) = self.images.get("thumbnail") {
+                let _ = write!(
+                    html,
+                    "<img class=\"thumbnail\" src=\"{}\" alt=\"thumbnail\"/>",
+                    data_uri(png)
+                );
+            }
+        }
+        html.push_str("</div>");
+
+        // boarding passes and coupons show auxiliary fields above secondary ones
+        let rows = match self.pass.style {
+            Style::BoardingPass(_) => [structure.auxiliary_fields(), structure.secondary_fields()],
+            _ => [structure.secondary_fields(), structure.auxiliary_fields()],
+        };
+        for fields in rows.iter().filter(|fields| !fields.is_empty()) {
+            html.push_str("<div class=\"row\">");
+            for field in fields.iter() {
+                html.push_str(&field_html(field));
+            }
+            html.push_str("</div>");
+        }
+
+        if let Style::BoardingPass(_) = self.pass.style {
+            if let Some(png) = self.images.get("footer") {
+                let _ = write!(
+                    html,
+                    "<div class=\"row\"><img src=\"{}\" alt=\"footer\"/></div>",
+                    data_uri(png)
+                );
+            }
+        }
+        html.push_str(&self.barcode_html());
+        html.push_str("</div>");
+
+        if !structure.back_fields().is_empty() {
+            html.push_str("<div class=\"pass back\"><div class=\"row\"><div>");
+            for field in structure.back_fields() {
+                html.push_str(&field_html(field));
+            }
+            html.push_str("</div></div></div>");
+        }
+        html
+    }
+
+    #[cfg(feature = "render")]
+    fn barcode_html(&self) -> String {
+        match self.pass.printable_barcode() {
+            Some(barcode) => match barcode.to_svg() {
+                Ok(svg) => format!("<div class=\"barcode\">{}</div>", svg),
+                Err(_) => self.barcode_placeholder(),
+            },
+            None => self.barcode_placeholder(),
+        }
+    }
+
+    #[cfg(not(feature = "render"))]
+    fn barcode_html(&self) -> String {
+        self.barcode_placeholder()
+    }
+
+    /// Barcode message in a box, when barcode image can't be rendered
+    fn barcode_placeholder(&self) -> String {
+        match self.pass.barcodes().first() {
+            Some(barcode) => format!(
+                "<div class=\"barcode\">{:?}<br/>{}</div>",
+                barcode.format,
+                escape_html(barcode.alt_text.as_ref().unwrap_or(&barcode.message))
+            ),
+            None => String::new(),
+        }
+    }
+
+    /// Rough height of the front and back for the SVG canvas
+    fn estimated_height(&self) -> usize {
+        let structure = self.pass.style.structure();
+        let front = 40 + 60 + 50 + 50 + 200;
+        let back = match structure.back_fields().len() {
+            0 => 0,
+            count => 40 + 60 * count,
+        };
+        front + back
+    }
+}
+
+fn field_html(field: &Field) -> String {
+    let alignment = match field.text_alignment {
+        TextAlignment::Left => "left",
+        TextAlignment::Center => "center",
+        TextAlignment::Right => "right",
+        TextAlignment::Natural => "start",
+    };
+    let value = match &field.attributed_value {
+        Some(attributed) => sanitize_attributed_value(attributed),
+        None => escape_html(&field.value.to_string()),
+    };
+    format!(
+        "<div class=\"field\" style=\"text-align:{}\"><div class=\"label\">{}</div><div class=\"value\">{}</div></div>",
+        alignment,
+        escape_html(field.label.as_ref().map_or("", String::as_str)),
+        value
+    )
+}
+
+fn transit_symbol(transit_type: Option<&TransitType>) -> &'static str {
+    match transit_type {
+        Some(TransitType::Air) => "&#x2708;",
+        Some(TransitType::Boat) => "&#x26F4;",
+        Some(TransitType::Bus) => "&#x1F68C;",
+        Some(TransitType::Train) => "&#x1F686;",
+        Some(TransitType::Generic) | None => "&#x2192;",
+    }
+}
+
+fn data_uri(png: &[u8]) -> String {
+    format!("data:image/png;base64,{}", base64::encode_block(png))
+}
+
+mod test {
+    #[test]
+    fn boarding_pass_preview() {
+        use super::*;
+        use {BarcodeFormat, PassBuilder};
+
+        let pass = PassBuilder::new("0001", "pass.com.example", "CDHE9L6U22")
+            .description("Skyport Boarding Pass")
+            .logo_text("Skyport <Airways>")
+            .background_color("rgb(22, 55, 110)")
+            .add_header_field(("gate", "GATE", "23"))
+            .add_primary_field(("depart", "SAN FRANCISCO", "SFO"))
+            .add_primary_field(("arrive", "NEW YORK", "JFK"))
+            .add_auxiliary_field(("seat", "SEAT", "7A"))
+            .add_back_field(("terms", "TERMS", "Non refundable"))
+            .add_barcode((BarcodeFormat::PDF417, "SFOJFK JOHN APPLESEED"))
+            .finish_boarding_pass(TransitType::Air);
+
+        let html = Preview::new(&pass)
+            .image("logo", b"\x89PNG".to_vec())
+            .to_html();
+        assert!(html.contains("color:rgb(0, 0, 0)"));
+        assert!(html.contains("Skyport &lt;Airways&gt;"));
+        assert!(html.contains("rgb(22, 55, 110)"));
+        assert!(html.contains("data:image/png;base64,iVBORw=="));
+        assert!(html.contains("&#x2708;"));
+        assert!(html.contains("Non refundable"));
+        assert!(html.find("SFO").unwrap() < html.find("JFK").unwrap());
+        if cfg!(feature = "render") {
+            assert!(html.contains("<div class=\"barcode\"><svg"));
+        } else {
+            assert!(html.contains("PDF417<br/>SFOJFK JOHN APPLESEED"));
+        }
+
+        let svg = Preview::new(&pass).to_svg();
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("foreignObject"));
+    }
+
+    #[test]
+    fn untrusted_colors_and_links() {
+        use super::*;
+        use {AttributedValue, FieldBuilder, PassBuilder};
+
+        let link = FieldBuilder::attributed(
+            "support",
+            AttributedValue::new()
+                .text("Questions? ")
+                .link("Call us", "tel:+18005550100"),
+        );
+        let mut script = FieldBuilder::new("script", "script").finish();
+        script.attributed_value = Some("<a href='javascript:alert(1)'>x</a>".into());
+        let pass = PassBuilder::new("0001", "pass.com.example", "CDHE9L6U22")
+            .background_color("red}body{display:none")
+            .foreground_color("rgb(300, 0, 0)")
+            .label_color(" rgb(1,2,3) ".into())
+            .add_back_field(link)
+            .add_back_field(script)
+            .finish_generic();
+
+        let html = Preview::new(&pass).to_html();
+        assert!(!html.contains("display:none"));
+        assert!(html.contains("background:rgb(255, 255, 255);color:rgb(0, 0, 0)"));
+        assert!(html.contains("color:rgb(1, 2, 3)"));
+        assert!(html.contains("Questions? <a href='tel:+18005550100'>Call us</a>"));
+        assert!(!html.contains("<a href='javascript"));
+    }
+}
@@ -0,0 +1,178 @@
+use pass::{Beacon, Location, Pass, Style};
+use {PassError, PassResult};
+
+/// Wallet uses only the first 10 locations of a pass
+pub const MAX_LOCATIONS: usize = 10;
+
+/// Wallet uses only the first 10 beacons of a pass
+pub const MAX_BEACONS: usize = 10;
+
+impl Location {
+    pub fn new(latitude: f64, longitude: f64) -> Location {
+        Location {
+            latitude: Some(latitude),
+            longitude: Some(longitude),
+            ..Default::default()
+        }
+    }
+
+    pub fn altitude(mut self, altitude: f64) -> Location {
+        self.altitude = Some(altitude);
+        self
+    }
+
+    pub fn relevant_text<T: Into<String>>(mut self, text: T) -> Location {
+        self.relevant_text = Some(text.into());
+        self
+    }
+
+    /// Check that latitude and longitude are present and in range
+    pub fn validate(&self) -> PassResult<()> {
+        match (self.latitude, self.longitude) {
+            (Some(latitude), Some(longitude)) => {
+                if !(-90.0..=90.0).contains(&latitude) {
+                    Err(PassError::InvalidRelevance(
+                        "latitude must be between -90 and 90",
+                    ))
+                } else if !(-180.0..=180.0).contains(&longitude) {
+                    Err(PassError::InvalidRelevance(
+                        "longitude must be between -180 and 180",
+                    ))
+                } else {
+                    Ok(())
+                }
+            }
+            _ => Err(PassError::InvalidRelevance(
+                "location requires latitude and longitude",
+            )),
+        }
+    }
+}
+
+impl Beacon {
+    pub fn new<U: Into<String>>(proximity_uuid: U) -> Beacon {
+        Beacon {
+            proximity_uuid: proximity_uuid.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn major(mut self, major: u16) -> Beacon {
+        self.major = Some(major);
+        self
+    }
+
+    pub fn minor(mut self, minor: u16) -> Beacon {
+        self.minor = Some(minor);
+        self
+    }
+
+    pub fn relevant_text<T: Into<String>>(mut self, text: T) -> Beacon {
+        self.relevant_text = Some(text.into());
+        self
+    }
+
+    /// Check that proximity UUID is formatted like `E2C56DB5-DFFB-48D2-B060-D0F5A71096E0`,
+    /// and minor is set only together with major
+    pub fn validate(&self) -> PassResult<()> {
+        let groups: Vec<&str> = self.proximity_uuid.split('-').collect();
+        let lengths: Vec<usize> = groups.iter().map(|group| group.len()).collect();
+        let hex = groups
+            .iter()
+            .all(|group| group.chars().all(|char| char.is_ascii_hexdigit()));
+        if lengths != [8, 4, 4, 4, 12] || !hex {
+            return Err(PassError::InvalidRelevance(
+                "beacon proximity UUID is malformed",
+            ));
+        }
+        if self.minor.is_some() && self.major.is_none() {
+            return Err(PassError::InvalidRelevance("beacon minor requires major"));
+        }
+        Ok(())
+    }
+}
+
+impl Style {
+    /// Approximate distance in meters from a location at which Wallet shows the pass.
+    /// `maxDistance` larger than this has no effect.
+    pub fn relevant_distance(&self) -> u32 {
+        match self {
+            Style::BoardingPass(_) | Style::EventTicket(_) => 1000,
+            Style::Coupon(_) | Style::Generic(_) | Style::StoreCard(_) => 100,
+        }
+    }
+}
+
+impl Pass {
+    /// Distance Wallet actually uses, the smaller of `max_distance` and the style's default
+    pub fn effective_max_distance(&self) -> u32 {
+        let default = self.style.relevant_distance();
+        self.max_distance.map_or(default, |max| max.min(default))
+    }
+
+    pub(crate) fn relevance_violations(&self) -> Vec<String> {
+        let mut violations = Vec::new();
+        if self.locations.len() > MAX_LOCATIONS {
+            violations.push(format!("more than {} locations", MAX_LOCATIONS));
+        }
+        if self.beacons.len() > MAX_BEACONS {
+            violations.push(format!("more than {} beacons", MAX_BEACONS));
+        }
+        let errors = self
+            .locations
+            .iter()
+            .map(Location::validate)
+            .chain(self.beacons.iter().map(Beacon::validate));
+        for error in errors.filter_map(Result::err) {
+            violations.push(error.to_string());
+        }
+        violations
+    }
+}
+
+mod test {
+    #[test]
+    fn locations_and_beacons() {
+        use super::*;
+        use PassBuilder;
+
+        let location = Location::new(37.6189722, -122.3748889).relevant_text("SFO nearby");
+        assert!(location.validate().is_ok());
+        assert!(Location::new(122.37, 37.61).validate().is_err());
+        assert!(Location::new(37.61, -190.0).validate().is_err());
+        let json = serde_json::to_value(Location::new(37.33, -122.03)).unwrap();
+        assert_eq!(json["latitude"], 37.33);
+        assert_eq!(json["longitude"], -122.03);
+
+        let beacon = Beacon::new("E2C56DB5-DFFB-48D2-B060-D0F5A71096E0").major(1);
+        assert!(beacon.validate().is_ok());
+        assert!(Beacon::new("E2C56DB5DFFB48D2B060D0F5A71096E0")
+            .validate()
+            .is_err());
+        assert!(Beacon::new("E2C56DB5-DFFB-48D2-B060-D0F5A71096EZ")
+            .validate()
+            .is_err());
+        assert!(beacon.clone().minor(2).validate().is_ok());
+        assert!(Beacon::new("E2C56DB5-DFFB-48D2-B060-D0F5A71096E0")
+            .minor(2)
+            .validate()
+            .is_err());
+
+        let mut builder = PassBuilder::new("0001", "pass.com.example", "CDHE9L6U22")
+            .organization_name("Coffee Shop")
+            .description("Rewards card")
+            .add_beacon(beacon)
+            .max_distance(500);
+        for _ in 0..=MAX_LOCATIONS {
+            builder = builder.add_location(location.clone());
+        }
+        let pass = builder.finish_store_card();
+        assert_eq!(pass.effective_max_distance(), 100);
+        match pass.validate() {
+            Err(PassError::InvalidPass(violations)) => {
+                assert_eq!(violations, vec!["more than 10 locations"])
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,1350 @@
+use pass::{Barcode, BarcodeFormat, Pass};
+use png::{BitDepth, ColorType, Encoder};
+use qrcode::{Color, EcLevel, QrCode};
+use std::fmt::Write;
+use util::escape_html;
+use {PassError, PassResult};
+
+/// Height of Code 128 bars, in modules
+const CODE128_HEIGHT: usize = 50;
+
+/// Bar and space widths of Code 128 symbols, indexed by symbol value
+const CODE128_PATTERNS: [&str; 106] = [
+    "212222", "222122", "222221", "121223", "121322", "131222", "122213", "122312", "132212",
+    "221213", "221312", "231212", "112232", "122132", "122231", "113222", "123122", "123221",
+    "223211", "221132", "221231", "213212", "223112", "312131", "311222", "321122", "321221",
+    "312212", "322112", "322211", "212123", "212321", "232121", "111323", "131123", "131321",
+    "112313", "132113", "132311", "211313", "231113", "231311", "112133", "112331", "132131",
+    "113123", "113321", "133121", "313121", "211331", "231131", "213113", "213311", "213131",
+    "311123", "311321", "331121", "312113", "312311", "332111", "314111", "221411", "431111",
+    "111224", "111422", "121124", "121421", "141122", "141221", "112214", "112412", "122114",
+    "122411", "142112", "142211", "241211", "221114", "413111", "241112", "134111", "111242",
+    "121142", "121241", "114212", "124112", "124211", "411212", "421112", "421211", "212141",
+    "214121", "412121", "111143", "111341", "131141", "114113", "114311", "411113", "411311",
+    "113141", "114131", "311141", "411131", "211412", "211214", "211232",
+];
+const CODE128_STOP: &str = "2331112";
+const CODE128_START_A: usize = 103;
+const CODE128_START_B: usize = 104;
+const CODE128_CODE_A: usize = 101;
+const CODE128_CODE_B: usize = 100;
+
+/// Longest message of one Aztec binary shift
+const AZTEC_BINARY_SHIFT_MAX: usize = 31 + 2047;
+
+/// Height of PDF417 rows, in modules
+const PDF417_ROW_HEIGHT: usize = 3;
+
+/// Bars and spaces 81111113 and 711311121
+const PDF417_START: usize = 0x1fea8;
+const PDF417_STOP: usize = 0x3fa29;
+
+/// Characters of PDF417 text compaction submodes, indexed by value, space is 26 in all of them
+const PDF417_MIXED: &[u8] = b"0123456789&\r\t,:#-.$/+%*=^";
+const PDF417_PUNCTUATION: &[u8] = b";<>@[\\]_`~!\r\t,:\n-.$/\"|*()?{}'";
+
+/// Bars and spaces of PDF417 codewords as 17 modules from the highest bit,
+/// for rows of clusters 0, 3 and 6 following each other
+const PDF417_PATTERNS: [[u32; 929]; 3] = [
+    [
+        0x1d5c0, 0x1eaf0, 0x1f57c, 0x1d4e0, 0x1ea78, 0x1f53e, 0x1a8c0, 0x1d470, 0x1a860, 0x15040,
+        0x1a830, 0x15020, 0x1adc0, 0x1d6f0, 0x1eb7c, 0x1ace0, 0x1d678, 0x1eb3e, 0x158c0, 0x1ac70,
+        0x15860, 0x15dc0, 0x1aef0, 0x1d77c, 0x15ce0, 0x1ae78, 0x1d73e, 0x15c70, 0x1ae3c, 0x15ef0,
+        0x1af7c, 0x15e78, 0x1af3e, 0x15f7c, 0x1f5fa, 0x1d2e0, 0x1e978, 0x1f4be, 0x1a4c0, 0x1d270,
+        0x1e93c, 0x1a460, 0x1d238, 0x14840, 0x1a430, 0x1d21c, 0x14820, 0x1a418, 0x14810, 0x1a6e0,
+        0x1d378, 0x1e9be, 0x14cc0, 0x1a670, 0x1d33c, 0x14c60, 0x1a638, 0x1d31e, 0x14c30, 0x1a61c,
+        0x14ee0, 0x1a778, 0x1d3be, 0x14e70, 0x1a73c, 0x14e38, 0x1a71e, 0x14f78, 0x1a7be, 0x14f3c,
+        0x14f1e, 0x1a2c0, 0x1d170, 0x1e8bc, 0x1a260, 0x1d138, 0x1e89e, 0x14440, 0x1a230, 0x1d11c,
+        0x14420, 0x1a218, 0x14410, 0x14408, 0x146c0, 0x1a370, 0x1d1bc, 0x14660, 0x1a338, 0x1d19e,
+        0x14630, 0x1a31c, 0x14618, 0x1460c, 0x14770, 0x1a3bc, 0x14738, 0x1a39e, 0x1471c, 0x147bc,
+        0x1a160, 0x1d0b8, 0x1e85e, 0x14240, 0x1a130, 0x1d09c, 0x14220, 0x1a118, 0x1d08e, 0x14210,
+        0x1a10c, 0x14208, 0x1a106, 0x14360, 0x1a1b8, 0x1d0de, 0x14330, 0x1a19c, 0x14318, 0x1a18e,
+        0x1430c, 0x14306, 0x1a1de, 0x1438e, 0x14140, 0x1a0b0, 0x1d05c, 0x14120, 0x1a098, 0x1d04e,
+        0x14110, 0x1a08c, 0x14108, 0x1a086, 0x14104, 0x141b0, 0x14198, 0x1418c, 0x140a0, 0x1d02e,
+        0x1a04c, 0x1a046, 0x14082, 0x1cae0, 0x1e578, 0x1f2be, 0x194c0, 0x1ca70, 0x1e53c, 0x19460,
+        0x1ca38, 0x1e51e, 0x12840, 0x19430, 0x12820, 0x196e0, 0x1cb78, 0x1e5be, 0x12cc0, 0x19670,
+        0x1cb3c, 0x12c60, 0x19638, 0x12c30, 0x12c18, 0x12ee0, 0x19778, 0x1cbbe, 0x12e70, 0x1973c,
+        0x12e38, 0x12e1c, 0x12f78, 0x197be, 0x12f3c, 0x12fbe, 0x1dac0, 0x1ed70, 0x1f6bc, 0x1da60,
+        0x1ed38, 0x1f69e, 0x1b440, 0x1da30, 0x1ed1c, 0x1b420, 0x1da18, 0x1ed0e, 0x1b410, 0x1da0c,
+        0x192c0, 0x1c970, 0x1e4bc, 0x1b6c0, 0x19260, 0x1c938, 0x1e49e, 0x1b660, 0x1db38, 0x1ed9e,
+        0x16c40, 0x12420, 0x19218, 0x1c90e, 0x16c20, 0x1b618, 0x16c10, 0x126c0, 0x19370, 0x1c9bc,
+        0x16ec0, 0x12660, 0x19338, 0x1c99e, 0x16e60, 0x1b738, 0x1db9e, 0x16e30, 0x12618, 0x16e18,
+        0x12770, 0x193bc, 0x16f70, 0x12738, 0x1939e, 0x16f38, 0x1b79e, 0x16f1c, 0x127bc, 0x16fbc,
+        0x1279e, 0x16f9e, 0x1d960, 0x1ecb8, 0x1f65e, 0x1b240, 0x1d930, 0x1ec9c, 0x1b220, 0x1d918,
+        0x1ec8e, 0x1b210, 0x1d90c, 0x1b208, 0x1b204, 0x19160, 0x1c8b8, 0x1e45e, 0x1b360, 0x19130,
+        0x1c89c, 0x16640, 0x12220, 0x1d99c, 0x1c88e, 0x16620, 0x12210, 0x1910c, 0x16610, 0x1b30c,
+        0x19106, 0x12204, 0x12360, 0x191b8, 0x1c8de, 0x16760, 0x12330, 0x1919c, 0x16730, 0x1b39c,
+        0x1918e, 0x16718, 0x1230c, 0x12306, 0x123b8, 0x191de, 0x167b8, 0x1239c, 0x1679c, 0x1238e,
+        0x1678e, 0x167de, 0x1b140, 0x1d8b0, 0x1ec5c, 0x1b120, 0x1d898, 0x1ec4e, 0x1b110, 0x1d88c,
+        0x1b108, 0x1d886, 0x1b104, 0x1b102, 0x12140, 0x190b0, 0x1c85c, 0x16340, 0x12120, 0x19098,
+        0x1c84e, 0x16320, 0x1b198, 0x1d8ce, 0x16310, 0x12108, 0x19086, 0x16308, 0x1b186, 0x16304,
+        0x121b0, 0x190dc, 0x163b0, 0x12198, 0x190ce, 0x16398, 0x1b1ce, 0x1638c, 0x12186, 0x16386,
+        0x163dc, 0x163ce, 0x1b0a0, 0x1d858, 0x1ec2e, 0x1b090, 0x1d84c, 0x1b088, 0x1d846, 0x1b084,
+        0x1b082, 0x120a0, 0x19058, 0x1c82e, 0x161a0, 0x12090, 0x1904c, 0x16190, 0x1b0cc, 0x19046,
+        0x16188, 0x12084, 0x16184, 0x12082, 0x120d8, 0x161d8, 0x161cc, 0x161c6, 0x1d82c, 0x1d826,
+        0x1b042, 0x1902c, 0x12048, 0x160c8, 0x160c4, 0x160c2, 0x18ac0, 0x1c570, 0x1e2bc, 0x18a60,
+        0x1c538, 0x11440, 0x18a30, 0x1c51c, 0x11420, 0x18a18, 0x11410, 0x11408, 0x116c0, 0x18b70,
+        0x1c5bc, 0x11660, 0x18b38, 0x1c59e, 0x11630, 0x18b1c, 0x11618, 0x1160c, 0x11770, 0x18bbc,
+        0x11738, 0x18b9e, 0x1171c, 0x117bc, 0x1179e, 0x1cd60, 0x1e6b8, 0x1f35e, 0x19a40, 0x1cd30,
+        0x1e69c, 0x19a20, 0x1cd18, 0x1e68e, 0x19a10, 0x1cd0c, 0x19a08, 0x1cd06, 0x18960, 0x1c4b8,
+        0x1e25e, 0x19b60, 0x18930, 0x1c49c, 0x13640, 0x11220, 0x1cd9c, 0x1c48e, 0x13620, 0x19b18,
+        0x1890c, 0x13610, 0x11208, 0x13608, 0x11360, 0x189b8, 0x1c4de, 0x13760, 0x11330, 0x1cdde,
+        0x13730, 0x19b9c, 0x1898e, 0x13718, 0x1130c, 0x1370c, 0x113b8, 0x189de, 0x137b8, 0x1139c,
+        0x1379c, 0x1138e, 0x113de, 0x137de, 0x1dd40, 0x1eeb0, 0x1f75c, 0x1dd20, 0x1ee98, 0x1f74e,
+        0x1dd10, 0x1ee8c, 0x1dd08, 0x1ee86, 0x1dd04, 0x19940, 0x1ccb0, 0x1e65c, 0x1bb40, 0x19920,
+        0x1eedc, 0x1e64e, 0x1bb20, 0x1dd98, 0x1eece, 0x1bb10, 0x19908, 0x1cc86, 0x1bb08, 0x1dd86,
+        0x19902, 0x11140, 0x188b0, 0x1c45c, 0x13340, 0x11120, 0x18898, 0x1c44e, 0x17740, 0x13320,
+        0x19998, 0x1ccce, 0x17720, 0x1bb98, 0x1ddce, 0x18886, 0x17710, 0x13308, 0x19986, 0x17708,
+        0x11102, 0x111b0, 0x188dc, 0x133b0, 0x11198, 0x188ce, 0x177b0, 0x13398, 0x199ce, 0x17798,
+        0x1bbce, 0x11186, 0x13386, 0x111dc, 0x133dc, 0x111ce, 0x177dc, 0x133ce, 0x1dca0, 0x1ee58,
+        0x1f72e, 0x1dc90, 0x1ee4c, 0x1dc88, 0x1ee46, 0x1dc84, 0x1dc82, 0x198a0, 0x1cc58, 0x1e62e,
+        0x1b9a0, 0x19890, 0x1ee6e, 0x1b990, 0x1dccc, 0x1cc46, 0x1b988, 0x19884, 0x1b984, 0x19882,
+        0x1b982, 0x110a0, 0x18858, 0x1c42e, 0x131a0, 0x11090, 0x1884c, 0x173a0, 0x13190, 0x198cc,
+        0x18846, 0x17390, 0x1b9cc, 0x11084, 0x17388, 0x13184, 0x11082, 0x13182, 0x110d8, 0x1886e,
+        0x131d8, 0x110cc, 0x173d8, 0x131cc, 0x110c6, 0x173cc, 0x131c6, 0x110ee, 0x173ee, 0x1dc50,
+        0x1ee2c, 0x1dc48, 0x1ee26, 0x1dc44, 0x1dc42, 0x19850, 0x1cc2c, 0x1b8d0, 0x19848, 0x1cc26,
+        0x1b8c8, 0x1dc66, 0x1b8c4, 0x19842, 0x1b8c2, 0x11050, 0x1882c, 0x130d0, 0x11048, 0x18826,
+        0x171d0, 0x130c8, 0x19866, 0x171c8, 0x1b8e6, 0x11042, 0x171c4, 0x130c2, 0x171c2, 0x130ec,
+        0x171ec, 0x171e6, 0x1ee16, 0x1dc22, 0x1cc16, 0x19824, 0x19822, 0x11028, 0x13068, 0x170e8,
+        0x11022, 0x13062, 0x18560, 0x10a40, 0x18530, 0x10a20, 0x18518, 0x1c28e, 0x10a10, 0x1850c,
+        0x10a08, 0x18506, 0x10b60, 0x185b8, 0x1c2de, 0x10b30, 0x1859c, 0x10b18, 0x1858e, 0x10b0c,
+        0x10b06, 0x10bb8, 0x185de, 0x10b9c, 0x10b8e, 0x10bde, 0x18d40, 0x1c6b0, 0x1e35c, 0x18d20,
+        0x1c698, 0x18d10, 0x1c68c, 0x18d08, 0x1c686, 0x18d04, 0x10940, 0x184b0, 0x1c25c, 0x11b40,
+        0x10920, 0x1c6dc, 0x1c24e, 0x11b20, 0x18d98, 0x1c6ce, 0x11b10, 0x10908, 0x18486, 0x11b08,
+        0x18d86, 0x10902, 0x109b0, 0x184dc, 0x11bb0, 0x10998, 0x184ce, 0x11b98, 0x18dce, 0x11b8c,
+        0x10986, 0x109dc, 0x11bdc, 0x109ce, 0x11bce, 0x1cea0, 0x1e758, 0x1f3ae, 0x1ce90, 0x1e74c,
+        0x1ce88, 0x1e746, 0x1ce84, 0x1ce82, 0x18ca0, 0x1c658, 0x19da0, 0x18c90, 0x1c64c, 0x19d90,
+        0x1cecc, 0x1c646, 0x19d88, 0x18c84, 0x19d84, 0x18c82, 0x19d82, 0x108a0, 0x18458, 0x119a0,
+        0x10890, 0x1c66e, 0x13ba0, 0x11990, 0x18ccc, 0x18446, 0x13b90, 0x19dcc, 0x10884, 0x13b88,
+        0x11984, 0x10882, 0x11982, 0x108d8, 0x1846e, 0x119d8, 0x108cc, 0x13bd8, 0x119cc, 0x108c6,
+        0x13bcc, 0x119c6, 0x108ee, 0x119ee, 0x13bee, 0x1ef50, 0x1f7ac, 0x1ef48, 0x1f7a6, 0x1ef44,
+        0x1ef42, 0x1ce50, 0x1e72c, 0x1ded0, 0x1ef6c, 0x1e726, 0x1dec8, 0x1ef66, 0x1dec4, 0x1ce42,
+        0x1dec2, 0x18c50, 0x1c62c, 0x19cd0, 0x18c48, 0x1c626, 0x1bdd0, 0x19cc8, 0x1ce66, 0x1bdc8,
+        0x1dee6, 0x18c42, 0x1bdc4, 0x19cc2, 0x1bdc2, 0x10850, 0x1842c, 0x118d0, 0x10848, 0x18426,
+        0x139d0, 0x118c8, 0x18c66, 0x17bd0, 0x139c8, 0x19ce6, 0x10842, 0x17bc8, 0x1bde6, 0x118c2,
+        0x17bc4, 0x1086c, 0x118ec, 0x10866, 0x139ec, 0x118e6, 0x17bec, 0x139e6, 0x17be6, 0x1ef28,
+        0x1f796, 0x1ef24, 0x1ef22, 0x1ce28, 0x1e716, 0x1de68, 0x1ef36, 0x1de64, 0x1ce22, 0x1de62,
+        0x18c28, 0x1c616, 0x19c68, 0x18c24, 0x1bce8, 0x19c64, 0x18c22, 0x1bce4, 0x19c62, 0x1bce2,
+        0x10828, 0x18416, 0x11868, 0x18c36, 0x138e8, 0x11864, 0x10822, 0x179e8, 0x138e4, 0x11862,
+        0x179e4, 0x138e2, 0x179e2, 0x11876, 0x179f6, 0x1ef12, 0x1de34, 0x1de32, 0x19c34, 0x1bc74,
+        0x1bc72, 0x11834, 0x13874, 0x178f4, 0x178f2, 0x10540, 0x10520, 0x18298, 0x10510, 0x10508,
+        0x10504, 0x105b0, 0x10598, 0x1058c, 0x10586, 0x105dc, 0x105ce, 0x186a0, 0x18690, 0x1c34c,
+        0x18688, 0x1c346, 0x18684, 0x18682, 0x104a0, 0x18258, 0x10da0, 0x186d8, 0x1824c, 0x10d90,
+        0x186cc, 0x10d88, 0x186c6, 0x10d84, 0x10482, 0x10d82, 0x104d8, 0x1826e, 0x10dd8, 0x186ee,
+        0x10dcc, 0x104c6, 0x10dc6, 0x104ee, 0x10dee, 0x1c750, 0x1c748, 0x1c744, 0x1c742, 0x18650,
+        0x18ed0, 0x1c76c, 0x1c326, 0x18ec8, 0x1c766, 0x18ec4, 0x18642, 0x18ec2, 0x10450, 0x10cd0,
+        0x10448, 0x18226, 0x11dd0, 0x10cc8, 0x10444, 0x11dc8, 0x10cc4, 0x10442, 0x11dc4, 0x10cc2,
+        0x1046c, 0x10cec, 0x10466, 0x11dec, 0x10ce6, 0x11de6, 0x1e7a8, 0x1e7a4, 0x1e7a2, 0x1c728,
+        0x1cf68, 0x1e7b6, 0x1cf64, 0x1c722, 0x1cf62, 0x18628, 0x1c316, 0x18e68, 0x1c736, 0x19ee8,
+        0x18e64, 0x18622, 0x19ee4, 0x18e62, 0x19ee2, 0x10428, 0x18216, 0x10c68, 0x18636, 0x11ce8,
+        0x10c64, 0x10422, 0x13de8, 0x11ce4, 0x10c62, 0x13de4, 0x11ce2, 0x10436, 0x10c76, 0x11cf6,
+        0x13df6, 0x1f7d4, 0x1f7d2, 0x1e794, 0x1efb4, 0x1e792, 0x1efb2, 0x1c714, 0x1cf34, 0x1c712,
+        0x1df74, 0x1cf32, 0x1df72, 0x18614, 0x18e34, 0x18612, 0x19e74, 0x18e32, 0x1bef4,
+    ],
+    [
+        0x1f560, 0x1fab8, 0x1ea40, 0x1f530, 0x1fa9c, 0x1ea20, 0x1f518, 0x1fa8e, 0x1ea10, 0x1f50c,
+        0x1ea08, 0x1f506, 0x1ea04, 0x1eb60, 0x1f5b8, 0x1fade, 0x1d640, 0x1eb30, 0x1f59c, 0x1d620,
+        0x1eb18, 0x1f58e, 0x1d610, 0x1eb0c, 0x1d608, 0x1eb06, 0x1d604, 0x1d760, 0x1ebb8, 0x1f5de,
+        0x1ae40, 0x1d730, 0x1eb9c, 0x1ae20, 0x1d718, 0x1eb8e, 0x1ae10, 0x1d70c, 0x1ae08, 0x1d706,
+        0x1ae04, 0x1af60, 0x1d7b8, 0x1ebde, 0x15e40, 0x1af30, 0x1d79c, 0x15e20, 0x1af18, 0x1d78e,
+        0x15e10, 0x1af0c, 0x15e08, 0x1af06, 0x15f60, 0x1afb8, 0x1d7de, 0x15f30, 0x1af9c, 0x15f18,
+        0x1af8e, 0x15f0c, 0x15fb8, 0x1afde, 0x15f9c, 0x15f8e, 0x1e940, 0x1f4b0, 0x1fa5c, 0x1e920,
+        0x1f498, 0x1fa4e, 0x1e910, 0x1f48c, 0x1e908, 0x1f486, 0x1e904, 0x1e902, 0x1d340, 0x1e9b0,
+        0x1f4dc, 0x1d320, 0x1e998, 0x1f4ce, 0x1d310, 0x1e98c, 0x1d308, 0x1e986, 0x1d304, 0x1d302,
+        0x1a740, 0x1d3b0, 0x1e9dc, 0x1a720, 0x1d398, 0x1e9ce, 0x1a710, 0x1d38c, 0x1a708, 0x1d386,
+        0x1a704, 0x1a702, 0x14f40, 0x1a7b0, 0x1d3dc, 0x14f20, 0x1a798, 0x1d3ce, 0x14f10, 0x1a78c,
+        0x14f08, 0x1a786, 0x14f04, 0x14fb0, 0x1a7dc, 0x14f98, 0x1a7ce, 0x14f8c, 0x14f86, 0x14fdc,
+        0x14fce, 0x1e8a0, 0x1f458, 0x1fa2e, 0x1e890, 0x1f44c, 0x1e888, 0x1f446, 0x1e884, 0x1e882,
+        0x1d1a0, 0x1e8d8, 0x1f46e, 0x1d190, 0x1e8cc, 0x1d188, 0x1e8c6, 0x1d184, 0x1d182, 0x1a3a0,
+        0x1d1d8, 0x1e8ee, 0x1a390, 0x1d1cc, 0x1a388, 0x1d1c6, 0x1a384, 0x1a382, 0x147a0, 0x1a3d8,
+        0x1d1ee, 0x14790, 0x1a3cc, 0x14788, 0x1a3c6, 0x14784, 0x14782, 0x147d8, 0x1a3ee, 0x147cc,
+        0x147c6, 0x147ee, 0x1e850, 0x1f42c, 0x1e848, 0x1f426, 0x1e844, 0x1e842, 0x1d0d0, 0x1e86c,
+        0x1d0c8, 0x1e866, 0x1d0c4, 0x1d0c2, 0x1a1d0, 0x1d0ec, 0x1a1c8, 0x1d0e6, 0x1a1c4, 0x1a1c2,
+        0x143d0, 0x1a1ec, 0x143c8, 0x1a1e6, 0x143c4, 0x143c2, 0x143ec, 0x143e6, 0x1e828, 0x1f416,
+        0x1e824, 0x1e822, 0x1d068, 0x1e836, 0x1d064, 0x1d062, 0x1a0e8, 0x1d076, 0x1a0e4, 0x1a0e2,
+        0x141e8, 0x1a0f6, 0x141e4, 0x141e2, 0x1e814, 0x1e812, 0x1d034, 0x1d032, 0x1a074, 0x1a072,
+        0x1e540, 0x1f2b0, 0x1f95c, 0x1e520, 0x1f298, 0x1f94e, 0x1e510, 0x1f28c, 0x1e508, 0x1f286,
+        0x1e504, 0x1e502, 0x1cb40, 0x1e5b0, 0x1f2dc, 0x1cb20, 0x1e598, 0x1f2ce, 0x1cb10, 0x1e58c,
+        0x1cb08, 0x1e586, 0x1cb04, 0x1cb02, 0x19740, 0x1cbb0, 0x1e5dc, 0x19720, 0x1cb98, 0x1e5ce,
+        0x19710, 0x1cb8c, 0x19708, 0x1cb86, 0x19704, 0x19702, 0x12f40, 0x197b0, 0x1cbdc, 0x12f20,
+        0x19798, 0x1cbce, 0x12f10, 0x1978c, 0x12f08, 0x19786, 0x12f04, 0x12fb0, 0x197dc, 0x12f98,
+        0x197ce, 0x12f8c, 0x12f86, 0x12fdc, 0x12fce, 0x1f6a0, 0x1fb58, 0x16bf0, 0x1f690, 0x1fb4c,
+        0x169f8, 0x1f688, 0x1fb46, 0x168fc, 0x1f684, 0x1f682, 0x1e4a0, 0x1f258, 0x1f92e, 0x1eda0,
+        0x1e490, 0x1fb6e, 0x1ed90, 0x1f6cc, 0x1f246, 0x1ed88, 0x1e484, 0x1ed84, 0x1e482, 0x1ed82,
+        0x1c9a0, 0x1e4d8, 0x1f26e, 0x1dba0, 0x1c990, 0x1e4cc, 0x1db90, 0x1edcc, 0x1e4c6, 0x1db88,
+        0x1c984, 0x1db84, 0x1c982, 0x1db82, 0x193a0, 0x1c9d8, 0x1e4ee, 0x1b7a0, 0x19390, 0x1c9cc,
+        0x1b790, 0x1dbcc, 0x1c9c6, 0x1b788, 0x19384, 0x1b784, 0x19382, 0x1b782, 0x127a0, 0x193d8,
+        0x1c9ee, 0x16fa0, 0x12790, 0x193cc, 0x16f90, 0x1b7cc, 0x193c6, 0x16f88, 0x12784, 0x16f84,
+        0x12782, 0x127d8, 0x193ee, 0x16fd8, 0x127cc, 0x16fcc, 0x127c6, 0x16fc6, 0x127ee, 0x1f650,
+        0x1fb2c, 0x165f8, 0x1f648, 0x1fb26, 0x164fc, 0x1f644, 0x1647e, 0x1f642, 0x1e450, 0x1f22c,
+        0x1ecd0, 0x1e448, 0x1f226, 0x1ecc8, 0x1f666, 0x1ecc4, 0x1e442, 0x1ecc2, 0x1c8d0, 0x1e46c,
+        0x1d9d0, 0x1c8c8, 0x1e466, 0x1d9c8, 0x1ece6, 0x1d9c4, 0x1c8c2, 0x1d9c2, 0x191d0, 0x1c8ec,
+        0x1b3d0, 0x191c8, 0x1c8e6, 0x1b3c8, 0x1d9e6, 0x1b3c4, 0x191c2, 0x1b3c2, 0x123d0, 0x191ec,
+        0x167d0, 0x123c8, 0x191e6, 0x167c8, 0x1b3e6, 0x167c4, 0x123c2, 0x167c2, 0x123ec, 0x167ec,
+        0x123e6, 0x167e6, 0x1f628, 0x1fb16, 0x162fc, 0x1f624, 0x1627e, 0x1f622, 0x1e428, 0x1f216,
+        0x1ec68, 0x1f636, 0x1ec64, 0x1e422, 0x1ec62, 0x1c868, 0x1e436, 0x1d8e8, 0x1c864, 0x1d8e4,
+        0x1c862, 0x1d8e2, 0x190e8, 0x1c876, 0x1b1e8, 0x1d8f6, 0x1b1e4, 0x190e2, 0x1b1e2, 0x121e8,
+        0x190f6, 0x163e8, 0x121e4, 0x163e4, 0x121e2, 0x163e2, 0x121f6, 0x163f6, 0x1f614, 0x1617e,
+        0x1f612, 0x1e414, 0x1ec34, 0x1e412, 0x1ec32, 0x1c834, 0x1d874, 0x1c832, 0x1d872, 0x19074,
+        0x1b0f4, 0x19072, 0x1b0f2, 0x120f4, 0x161f4, 0x120f2, 0x161f2, 0x1f60a, 0x1e40a, 0x1ec1a,
+        0x1c81a, 0x1d83a, 0x1903a, 0x1b07a, 0x1e2a0, 0x1f158, 0x1f8ae, 0x1e290, 0x1f14c, 0x1e288,
+        0x1f146, 0x1e284, 0x1e282, 0x1c5a0, 0x1e2d8, 0x1f16e, 0x1c590, 0x1e2cc, 0x1c588, 0x1e2c6,
+        0x1c584, 0x1c582, 0x18ba0, 0x1c5d8, 0x1e2ee, 0x18b90, 0x1c5cc, 0x18b88, 0x1c5c6, 0x18b84,
+        0x18b82, 0x117a0, 0x18bd8, 0x1c5ee, 0x11790, 0x18bcc, 0x11788, 0x18bc6, 0x11784, 0x11782,
+        0x117d8, 0x18bee, 0x117cc, 0x117c6, 0x117ee, 0x1f350, 0x1f9ac, 0x135f8, 0x1f348, 0x1f9a6,
+        0x134fc, 0x1f344, 0x1347e, 0x1f342, 0x1e250, 0x1f12c, 0x1e6d0, 0x1e248, 0x1f126, 0x1e6c8,
+        0x1f366, 0x1e6c4, 0x1e242, 0x1e6c2, 0x1c4d0, 0x1e26c, 0x1cdd0, 0x1c4c8, 0x1e266, 0x1cdc8,
+        0x1e6e6, 0x1cdc4, 0x1c4c2, 0x1cdc2, 0x189d0, 0x1c4ec, 0x19bd0, 0x189c8, 0x1c4e6, 0x19bc8,
+        0x1cde6, 0x19bc4, 0x189c2, 0x19bc2, 0x113d0, 0x189ec, 0x137d0, 0x113c8, 0x189e6, 0x137c8,
+        0x19be6, 0x137c4, 0x113c2, 0x137c2, 0x113ec, 0x137ec, 0x113e6, 0x137e6, 0x1fba8, 0x175f0,
+        0x1bafc, 0x1fba4, 0x174f8, 0x1ba7e, 0x1fba2, 0x1747c, 0x1743e, 0x1f328, 0x1f996, 0x132fc,
+        0x1f768, 0x1fbb6, 0x176fc, 0x1327e, 0x1f764, 0x1f322, 0x1767e, 0x1f762, 0x1e228, 0x1f116,
+        0x1e668, 0x1e224, 0x1eee8, 0x1f776, 0x1e222, 0x1eee4, 0x1e662, 0x1eee2, 0x1c468, 0x1e236,
+        0x1cce8, 0x1c464, 0x1dde8, 0x1cce4, 0x1c462, 0x1dde4, 0x1cce2, 0x1dde2, 0x188e8, 0x1c476,
+        0x199e8, 0x188e4, 0x1bbe8, 0x199e4, 0x188e2, 0x1bbe4, 0x199e2, 0x1bbe2, 0x111e8, 0x188f6,
+        0x133e8, 0x111e4, 0x177e8, 0x133e4, 0x111e2, 0x177e4, 0x133e2, 0x177e2, 0x111f6, 0x133f6,
+        0x1fb94, 0x172f8, 0x1b97e, 0x1fb92, 0x1727c, 0x1723e, 0x1f314, 0x1317e, 0x1f734, 0x1f312,
+        0x1737e, 0x1f732, 0x1e214, 0x1e634, 0x1e212, 0x1ee74, 0x1e632, 0x1ee72, 0x1c434, 0x1cc74,
+        0x1c432, 0x1dcf4, 0x1cc72, 0x1dcf2, 0x18874, 0x198f4, 0x18872, 0x1b9f4, 0x198f2, 0x1b9f2,
+        0x110f4, 0x131f4, 0x110f2, 0x173f4, 0x131f2, 0x173f2, 0x1fb8a, 0x1717c, 0x1713e, 0x1f30a,
+        0x1f71a, 0x1e20a, 0x1e61a, 0x1ee3a, 0x1c41a, 0x1cc3a, 0x1dc7a, 0x1883a, 0x1987a, 0x1b8fa,
+        0x1107a, 0x130fa, 0x171fa, 0x170be, 0x1e150, 0x1f0ac, 0x1e148, 0x1f0a6, 0x1e144, 0x1e142,
+        0x1c2d0, 0x1e16c, 0x1c2c8, 0x1e166, 0x1c2c4, 0x1c2c2, 0x185d0, 0x1c2ec, 0x185c8, 0x1c2e6,
+        0x185c4, 0x185c2, 0x10bd0, 0x185ec, 0x10bc8, 0x185e6, 0x10bc4, 0x10bc2, 0x10bec, 0x10be6,
+        0x1f1a8, 0x1f8d6, 0x11afc, 0x1f1a4, 0x11a7e, 0x1f1a2, 0x1e128, 0x1f096, 0x1e368, 0x1e124,
+        0x1e364, 0x1e122, 0x1e362, 0x1c268, 0x1e136, 0x1c6e8, 0x1c264, 0x1c6e4, 0x1c262, 0x1c6e2,
+        0x184e8, 0x1c276, 0x18de8, 0x184e4, 0x18de4, 0x184e2, 0x18de2, 0x109e8, 0x184f6, 0x11be8,
+        0x109e4, 0x11be4, 0x109e2, 0x11be2, 0x109f6, 0x11bf6, 0x1f9d4, 0x13af8, 0x19d7e, 0x1f9d2,
+        0x13a7c, 0x13a3e, 0x1f194, 0x1197e, 0x1f3b4, 0x1f192, 0x13b7e, 0x1f3b2, 0x1e114, 0x1e334,
+        0x1e112, 0x1e774, 0x1e332, 0x1e772, 0x1c234, 0x1c674, 0x1c232, 0x1cef4, 0x1c672, 0x1cef2,
+        0x18474, 0x18cf4, 0x18472, 0x19df4, 0x18cf2, 0x19df2, 0x108f4, 0x119f4, 0x108f2, 0x13bf4,
+        0x119f2, 0x13bf2, 0x17af0, 0x1bd7c, 0x17a78, 0x1bd3e, 0x17a3c, 0x17a1e, 0x1f9ca, 0x1397c,
+        0x1fbda, 0x17b7c, 0x1393e, 0x17b3e, 0x1f18a, 0x1f39a, 0x1f7ba, 0x1e10a, 0x1e31a, 0x1e73a,
+        0x1ef7a, 0x1c21a, 0x1c63a, 0x1ce7a, 0x1defa, 0x1843a, 0x18c7a, 0x19cfa, 0x1bdfa, 0x1087a,
+        0x118fa, 0x139fa, 0x17978, 0x1bcbe, 0x1793c, 0x1791e, 0x138be, 0x179be, 0x178bc, 0x1789e,
+        0x1785e, 0x1e0a8, 0x1e0a4, 0x1e0a2, 0x1c168, 0x1e0b6, 0x1c164, 0x1c162, 0x182e8, 0x1c176,
+        0x182e4, 0x182e2, 0x105e8, 0x182f6, 0x105e4, 0x105e2, 0x105f6, 0x1f0d4, 0x10d7e, 0x1f0d2,
+        0x1e094, 0x1e1b4, 0x1e092, 0x1e1b2, 0x1c134, 0x1c374, 0x1c132, 0x1c372, 0x18274, 0x186f4,
+        0x18272, 0x186f2, 0x104f4, 0x10df4, 0x104f2, 0x10df2, 0x1f8ea, 0x11d7c, 0x11d3e, 0x1f0ca,
+        0x1f1da, 0x1e08a, 0x1e19a, 0x1e3ba, 0x1c11a, 0x1c33a, 0x1c77a, 0x1823a, 0x1867a, 0x18efa,
+        0x1047a, 0x10cfa, 0x11dfa, 0x13d78, 0x19ebe, 0x13d3c, 0x13d1e, 0x11cbe, 0x13dbe, 0x17d70,
+        0x1bebc, 0x17d38, 0x1be9e, 0x17d1c, 0x17d0e, 0x13cbc, 0x17dbc, 0x13c9e, 0x17d9e, 0x17cb8,
+        0x1be5e, 0x17c9c, 0x17c8e, 0x13c5e, 0x17cde, 0x17c5c, 0x17c4e, 0x17c2e, 0x1c0b4, 0x1c0b2,
+        0x18174, 0x18172, 0x102f4, 0x102f2, 0x1e0da, 0x1c09a, 0x1c1ba, 0x1813a, 0x1837a, 0x1027a,
+        0x106fa, 0x10ebe, 0x11ebc, 0x11e9e, 0x13eb8, 0x19f5e, 0x13e9c, 0x13e8e, 0x11e5e, 0x13ede,
+        0x17eb0, 0x1bf5c, 0x17e98, 0x1bf4e, 0x17e8c, 0x17e86, 0x13e5c, 0x17edc, 0x13e4e, 0x17ece,
+        0x17e58, 0x1bf2e, 0x17e4c, 0x17e46, 0x13e2e, 0x17e6e, 0x17e2c, 0x17e26, 0x10f5e, 0x11f5c,
+        0x11f4e, 0x13f58, 0x19fae, 0x13f4c, 0x13f46, 0x11f2e, 0x13f6e, 0x13f2c, 0x13f26,
+    ],
+    [
+        0x1abe0, 0x1d5f8, 0x153c0, 0x1a9f0, 0x1d4fc, 0x151e0, 0x1a8f8, 0x1d47e, 0x150f0, 0x1a87c,
+        0x15078, 0x1fad0, 0x15be0, 0x1adf8, 0x1fac8, 0x159f0, 0x1acfc, 0x1fac4, 0x158f8, 0x1ac7e,
+        0x1fac2, 0x1587c, 0x1f5d0, 0x1faec, 0x15df8, 0x1f5c8, 0x1fae6, 0x15cfc, 0x1f5c4, 0x15c7e,
+        0x1f5c2, 0x1ebd0, 0x1f5ec, 0x1ebc8, 0x1f5e6, 0x1ebc4, 0x1ebc2, 0x1d7d0, 0x1ebec, 0x1d7c8,
+        0x1ebe6, 0x1d7c4, 0x1d7c2, 0x1afd0, 0x1d7ec, 0x1afc8, 0x1d7e6, 0x1afc4, 0x14bc0, 0x1a5f0,
+        0x1d2fc, 0x149e0, 0x1a4f8, 0x1d27e, 0x148f0, 0x1a47c, 0x14878, 0x1a43e, 0x1483c, 0x1fa68,
+        0x14df0, 0x1a6fc, 0x1fa64, 0x14cf8, 0x1a67e, 0x1fa62, 0x14c7c, 0x14c3e, 0x1f4e8, 0x1fa76,
+        0x14efc, 0x1f4e4, 0x14e7e, 0x1f4e2, 0x1e9e8, 0x1f4f6, 0x1e9e4, 0x1e9e2, 0x1d3e8, 0x1e9f6,
+        0x1d3e4, 0x1d3e2, 0x1a7e8, 0x1d3f6, 0x1a7e4, 0x1a7e2, 0x145e0, 0x1a2f8, 0x1d17e, 0x144f0,
+        0x1a27c, 0x14478, 0x1a23e, 0x1443c, 0x1441e, 0x1fa34, 0x146f8, 0x1a37e, 0x1fa32, 0x1467c,
+        0x1463e, 0x1f474, 0x1477e, 0x1f472, 0x1e8f4, 0x1e8f2, 0x1d1f4, 0x1d1f2, 0x1a3f4, 0x1a3f2,
+        0x142f0, 0x1a17c, 0x14278, 0x1a13e, 0x1423c, 0x1421e, 0x1fa1a, 0x1437c, 0x1433e, 0x1f43a,
+        0x1e87a, 0x1d0fa, 0x14178, 0x1a0be, 0x1413c, 0x1411e, 0x141be, 0x140bc, 0x1409e, 0x12bc0,
+        0x195f0, 0x1cafc, 0x129e0, 0x194f8, 0x1ca7e, 0x128f0, 0x1947c, 0x12878, 0x1943e, 0x1283c,
+        0x1f968, 0x12df0, 0x196fc, 0x1f964, 0x12cf8, 0x1967e, 0x1f962, 0x12c7c, 0x12c3e, 0x1f2e8,
+        0x1f976, 0x12efc, 0x1f2e4, 0x12e7e, 0x1f2e2, 0x1e5e8, 0x1f2f6, 0x1e5e4, 0x1e5e2, 0x1cbe8,
+        0x1e5f6, 0x1cbe4, 0x1cbe2, 0x197e8, 0x1cbf6, 0x197e4, 0x197e2, 0x1b5e0, 0x1daf8, 0x1ed7e,
+        0x169c0, 0x1b4f0, 0x1da7c, 0x168e0, 0x1b478, 0x1da3e, 0x16870, 0x1b43c, 0x16838, 0x1b41e,
+        0x1681c, 0x125e0, 0x192f8, 0x1c97e, 0x16de0, 0x124f0, 0x1927c, 0x16cf0, 0x1b67c, 0x1923e,
+        0x16c78, 0x1243c, 0x16c3c, 0x1241e, 0x16c1e, 0x1f934, 0x126f8, 0x1937e, 0x1fb74, 0x1f932,
+        0x16ef8, 0x1267c, 0x1fb72, 0x16e7c, 0x1263e, 0x16e3e, 0x1f274, 0x1277e, 0x1f6f4, 0x1f272,
+        0x16f7e, 0x1f6f2, 0x1e4f4, 0x1edf4, 0x1e4f2, 0x1edf2, 0x1c9f4, 0x1dbf4, 0x1c9f2, 0x1dbf2,
+        0x193f4, 0x193f2, 0x165c0, 0x1b2f0, 0x1d97c, 0x164e0, 0x1b278, 0x1d93e, 0x16470, 0x1b23c,
+        0x16438, 0x1b21e, 0x1641c, 0x1640e, 0x122f0, 0x1917c, 0x166f0, 0x12278, 0x1913e, 0x16678,
+        0x1b33e, 0x1663c, 0x1221e, 0x1661e, 0x1f91a, 0x1237c, 0x1fb3a, 0x1677c, 0x1233e, 0x1673e,
+        0x1f23a, 0x1f67a, 0x1e47a, 0x1ecfa, 0x1c8fa, 0x1d9fa, 0x191fa, 0x162e0, 0x1b178, 0x1d8be,
+        0x16270, 0x1b13c, 0x16238, 0x1b11e, 0x1621c, 0x1620e, 0x12178, 0x190be, 0x16378, 0x1213c,
+        0x1633c, 0x1211e, 0x1631e, 0x121be, 0x163be, 0x16170, 0x1b0bc, 0x16138, 0x1b09e, 0x1611c,
+        0x1610e, 0x120bc, 0x161bc, 0x1209e, 0x1619e, 0x160b8, 0x1b05e, 0x1609c, 0x1608e, 0x1205e,
+        0x160de, 0x1605c, 0x1604e, 0x115e0, 0x18af8, 0x1c57e, 0x114f0, 0x18a7c, 0x11478, 0x18a3e,
+        0x1143c, 0x1141e, 0x1f8b4, 0x116f8, 0x18b7e, 0x1f8b2, 0x1167c, 0x1163e, 0x1f174, 0x1177e,
+        0x1f172, 0x1e2f4, 0x1e2f2, 0x1c5f4, 0x1c5f2, 0x18bf4, 0x18bf2, 0x135c0, 0x19af0, 0x1cd7c,
+        0x134e0, 0x19a78, 0x1cd3e, 0x13470, 0x19a3c, 0x13438, 0x19a1e, 0x1341c, 0x1340e, 0x112f0,
+        0x1897c, 0x136f0, 0x11278, 0x1893e, 0x13678, 0x19b3e, 0x1363c, 0x1121e, 0x1361e, 0x1f89a,
+        0x1137c, 0x1f9ba, 0x1377c, 0x1133e, 0x1373e, 0x1f13a, 0x1f37a, 0x1e27a, 0x1e6fa, 0x1c4fa,
+        0x1cdfa, 0x189fa, 0x1bae0, 0x1dd78, 0x1eebe, 0x174c0, 0x1ba70, 0x1dd3c, 0x17460, 0x1ba38,
+        0x1dd1e, 0x17430, 0x1ba1c, 0x17418, 0x1ba0e, 0x1740c, 0x132e0, 0x19978, 0x1ccbe, 0x176e0,
+        0x13270, 0x1993c, 0x17670, 0x1bb3c, 0x1991e, 0x17638, 0x1321c, 0x1761c, 0x1320e, 0x1760e,
+        0x11178, 0x188be, 0x13378, 0x1113c, 0x17778, 0x1333c, 0x1111e, 0x1773c, 0x1331e, 0x1771e,
+        0x111be, 0x133be, 0x177be, 0x172c0, 0x1b970, 0x1dcbc, 0x17260, 0x1b938, 0x1dc9e, 0x17230,
+        0x1b91c, 0x17218, 0x1b90e, 0x1720c, 0x17206, 0x13170, 0x198bc, 0x17370, 0x13138, 0x1989e,
+        0x17338, 0x1b99e, 0x1731c, 0x1310e, 0x1730e, 0x110bc, 0x131bc, 0x1109e, 0x173bc, 0x1319e,
+        0x1739e, 0x17160, 0x1b8b8, 0x1dc5e, 0x17130, 0x1b89c, 0x17118, 0x1b88e, 0x1710c, 0x17106,
+        0x130b8, 0x1985e, 0x171b8, 0x1309c, 0x1719c, 0x1308e, 0x1718e, 0x1105e, 0x130de, 0x171de,
+        0x170b0, 0x1b85c, 0x17098, 0x1b84e, 0x1708c, 0x17086, 0x1305c, 0x170dc, 0x1304e, 0x170ce,
+        0x17058, 0x1b82e, 0x1704c, 0x17046, 0x1302e, 0x1706e, 0x1702c, 0x17026, 0x10af0, 0x1857c,
+        0x10a78, 0x1853e, 0x10a3c, 0x10a1e, 0x10b7c, 0x10b3e, 0x1f0ba, 0x1e17a, 0x1c2fa, 0x185fa,
+        0x11ae0, 0x18d78, 0x1c6be, 0x11a70, 0x18d3c, 0x11a38, 0x18d1e, 0x11a1c, 0x11a0e, 0x10978,
+        0x184be, 0x11b78, 0x1093c, 0x11b3c, 0x1091e, 0x11b1e, 0x109be, 0x11bbe, 0x13ac0, 0x19d70,
+        0x1cebc, 0x13a60, 0x19d38, 0x1ce9e, 0x13a30, 0x19d1c, 0x13a18, 0x19d0e, 0x13a0c, 0x13a06,
+        0x11970, 0x18cbc, 0x13b70, 0x11938, 0x18c9e, 0x13b38, 0x1191c, 0x13b1c, 0x1190e, 0x13b0e,
+        0x108bc, 0x119bc, 0x1089e, 0x13bbc, 0x1199e, 0x13b9e, 0x1bd60, 0x1deb8, 0x1ef5e, 0x17a40,
+        0x1bd30, 0x1de9c, 0x17a20, 0x1bd18, 0x1de8e, 0x17a10, 0x1bd0c, 0x17a08, 0x1bd06, 0x17a04,
+        0x13960, 0x19cb8, 0x1ce5e, 0x17b60, 0x13930, 0x19c9c, 0x17b30, 0x1bd9c, 0x19c8e, 0x17b18,
+        0x1390c, 0x17b0c, 0x13906, 0x17b06, 0x118b8, 0x18c5e, 0x139b8, 0x1189c, 0x17bb8, 0x1399c,
+        0x1188e, 0x17b9c, 0x1398e, 0x17b8e, 0x1085e, 0x118de, 0x139de, 0x17bde, 0x17940, 0x1bcb0,
+        0x1de5c, 0x17920, 0x1bc98, 0x1de4e, 0x17910, 0x1bc8c, 0x17908, 0x1bc86, 0x17904, 0x17902,
+        0x138b0, 0x19c5c, 0x179b0, 0x13898, 0x19c4e, 0x17998, 0x1bcce, 0x1798c, 0x13886, 0x17986,
+        0x1185c, 0x138dc, 0x1184e, 0x179dc, 0x138ce, 0x179ce, 0x178a0, 0x1bc58, 0x1de2e, 0x17890,
+        0x1bc4c, 0x17888, 0x1bc46, 0x17884, 0x17882, 0x13858, 0x19c2e, 0x178d8, 0x1384c, 0x178cc,
+        0x13846, 0x178c6, 0x1182e, 0x1386e, 0x178ee, 0x17850, 0x1bc2c, 0x17848, 0x1bc26, 0x17844,
+        0x17842, 0x1382c, 0x1786c, 0x13826, 0x17866, 0x17828, 0x1bc16, 0x17824, 0x17822, 0x13816,
+        0x17836, 0x10578, 0x182be, 0x1053c, 0x1051e, 0x105be, 0x10d70, 0x186bc, 0x10d38, 0x1869e,
+        0x10d1c, 0x10d0e, 0x104bc, 0x10dbc, 0x1049e, 0x10d9e, 0x11d60, 0x18eb8, 0x1c75e, 0x11d30,
+        0x18e9c, 0x11d18, 0x18e8e, 0x11d0c, 0x11d06, 0x10cb8, 0x1865e, 0x11db8, 0x10c9c, 0x11d9c,
+        0x10c8e, 0x11d8e, 0x1045e, 0x10cde, 0x11dde, 0x13d40, 0x19eb0, 0x1cf5c, 0x13d20, 0x19e98,
+        0x1cf4e, 0x13d10, 0x19e8c, 0x13d08, 0x19e86, 0x13d04, 0x13d02, 0x11cb0, 0x18e5c, 0x13db0,
+        0x11c98, 0x18e4e, 0x13d98, 0x19ece, 0x13d8c, 0x11c86, 0x13d86, 0x10c5c, 0x11cdc, 0x10c4e,
+        0x13ddc, 0x11cce, 0x13dce, 0x1bea0, 0x1df58, 0x1efae, 0x1be90, 0x1df4c, 0x1be88, 0x1df46,
+        0x1be84, 0x1be82, 0x13ca0, 0x19e58, 0x1cf2e, 0x17da0, 0x13c90, 0x19e4c, 0x17d90, 0x1becc,
+        0x19e46, 0x17d88, 0x13c84, 0x17d84, 0x13c82, 0x17d82, 0x11c58, 0x18e2e, 0x13cd8, 0x11c4c,
+        0x17dd8, 0x13ccc, 0x11c46, 0x17dcc, 0x13cc6, 0x17dc6, 0x10c2e, 0x11c6e, 0x13cee, 0x17dee,
+        0x1be50, 0x1df2c, 0x1be48, 0x1df26, 0x1be44, 0x1be42, 0x13c50, 0x19e2c, 0x17cd0, 0x13c48,
+        0x19e26, 0x17cc8, 0x1be66, 0x17cc4, 0x13c42, 0x17cc2, 0x11c2c, 0x13c6c, 0x11c26, 0x17cec,
+        0x13c66, 0x17ce6, 0x1be28, 0x1df16, 0x1be24, 0x1be22, 0x13c28, 0x19e16, 0x17c68, 0x13c24,
+        0x17c64, 0x13c22, 0x17c62, 0x11c16, 0x13c36, 0x17c76, 0x1be14, 0x1be12, 0x13c14, 0x17c34,
+        0x13c12, 0x17c32, 0x102bc, 0x1029e, 0x106b8, 0x1835e, 0x1069c, 0x1068e, 0x1025e, 0x106de,
+        0x10eb0, 0x1875c, 0x10e98, 0x1874e, 0x10e8c, 0x10e86, 0x1065c, 0x10edc, 0x1064e, 0x10ece,
+        0x11ea0, 0x18f58, 0x1c7ae, 0x11e90, 0x18f4c, 0x11e88, 0x18f46, 0x11e84, 0x11e82, 0x10e58,
+        0x1872e, 0x11ed8, 0x18f6e, 0x11ecc, 0x10e46, 0x11ec6, 0x1062e, 0x10e6e, 0x11eee, 0x19f50,
+        0x1cfac, 0x19f48, 0x1cfa6, 0x19f44, 0x19f42, 0x11e50, 0x18f2c, 0x13ed0, 0x19f6c, 0x18f26,
+        0x13ec8, 0x11e44, 0x13ec4, 0x11e42, 0x13ec2, 0x10e2c, 0x11e6c, 0x10e26, 0x13eec, 0x11e66,
+        0x13ee6, 0x1dfa8, 0x1efd6, 0x1dfa4, 0x1dfa2, 0x19f28, 0x1cf96, 0x1bf68, 0x19f24, 0x1bf64,
+        0x19f22, 0x1bf62, 0x11e28, 0x18f16, 0x13e68, 0x11e24, 0x17ee8, 0x13e64, 0x11e22, 0x17ee4,
+        0x13e62, 0x17ee2, 0x10e16, 0x11e36, 0x13e76, 0x17ef6, 0x1df94, 0x1df92, 0x19f14, 0x1bf34,
+        0x19f12, 0x1bf32, 0x11e14, 0x13e34, 0x11e12, 0x17e74, 0x13e32, 0x17e72, 0x1df8a, 0x19f0a,
+        0x1bf1a, 0x11e0a, 0x13e1a, 0x17e3a, 0x1035c, 0x1034e, 0x10758, 0x183ae, 0x1074c, 0x10746,
+        0x1032e, 0x1076e, 0x10f50, 0x187ac, 0x10f48, 0x187a6, 0x10f44, 0x10f42, 0x1072c, 0x10f6c,
+        0x10726, 0x10f66, 0x18fa8, 0x1c7d6, 0x18fa4, 0x18fa2, 0x10f28, 0x18796, 0x11f68, 0x18fb6,
+        0x11f64, 0x10f22, 0x11f62, 0x10716, 0x10f36, 0x11f76, 0x1cfd4, 0x1cfd2, 0x18f94, 0x19fb4,
+        0x18f92, 0x19fb2, 0x10f14, 0x11f34, 0x10f12, 0x13f74, 0x11f32, 0x13f72, 0x1cfca, 0x18f8a,
+        0x19f9a, 0x10f0a, 0x11f1a, 0x13f3a, 0x103ac, 0x103a6, 0x107a8, 0x183d6, 0x107a4, 0x107a2,
+        0x10396, 0x107b6, 0x187d4, 0x187d2, 0x10794, 0x10fb4, 0x10792, 0x10fb2, 0x1c7ea,
+    ],
+];
+
+/// Height of the alt text line below the code, in modules
+const TEXT_HEIGHT: usize = 6;
+
+/// 5x7 glyphs of printable ASCII characters, columns from left with top pixel in lowest bit
+const FONT: [[u8; 5]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00], // space
+    [0x00, 0x00, 0x5F, 0x00, 0x00], // !
+    [0x00, 0x07, 0x00, 0x07, 0x00], // "
+    [0x14, 0x7F, 0x14, 0x7F, 0x14], // #
+    [0x24, 0x2A, 0x7F, 0x2A, 0x12], // $
+    [0x23, 0x13, 0x08, 0x64, 0x62], // %
+    [0x36, 0x49, 0x55, 0x22, 0x50], // &
+    [0x00, 0x05, 0x03, 0x00, 0x00], // '
+    [0x00, 0x1C, 0x22, 0x41, 0x00], // (
+    [0x00, 0x41, 0x22, 0x1C, 0x00], // )
+    [0x08, 0x2A, 0x1C, 0x2A, 0x08], // *
+    [0x08, 0x08, 0x3E, 0x08, 0x08], // +
+    [0x00, 0x50, 0x30, 0x00, 0x00], // ,
+    [0x08, 0x08, 0x08, 0x08, 0x08], // -
+    [0x00, 0x60, 0x60, 0x00, 0x00], // .
+    [0x20, 0x10, 0x08, 0x04, 0x02], // /
+    [0x3E, 0x51, 0x49, 0x45, 0x3E], // 0
+    [0x00, 0x42, 0x7F, 0x40, 0x00], // 1
+    [0x42, 0x61, 0x51, 0x49, 0x46], // 2
+    [0x21, 0x41, 0x45, 0x4B, 0x31], // 3
+    [0x18, 0x14, 0x12, 0x7F, 0x10], // 4
+    [0x27, 0x45, 0x45, 0x45, 0x39], // 5
+    [0x3C, 0x4A, 0x49, 0x49, 0x30], // 6
+    [0x01, 0x71, 0x09, 0x05, 0x03], // 7
+    [0x36, 0x49, 0x49, 0x49, 0x36], // 8
+    [0x06, 0x49, 0x49, 0x29, 0x1E], // 9
+    [0x00, 0x36, 0x36, 0x00, 0x00], // :
+    [0x00, 0x56, 0x36, 0x00, 0x00], // ;
+    [0x08, 0x14, 0x22, 0x41, 0x00], // <
+    [0x14, 0x14, 0x14, 0x14, 0x14], // =
+    [0x00, 0x41, 0x22, 0x14, 0x08], // >
+    [0x02, 0x01, 0x51, 0x09, 0x06], // ?
+    [0x32, 0x49, 0x79, 0x41, 0x3E], // @
+    [0x7E, 0x11, 0x11, 0x11, 0x7E], // A
+    [0x7F, 0x49, 0x49, 0x49, 0x36], // B
+    [0x3E, 0x41, 0x41, 0x41, 0x22], // C
+    [0x7F, 0x41, 0x41, 0x22, 0x1C], // D
+    [0x7F, 0x49, 0x49, 0x49, 0x41], // E
+    [0x7F, 0x09, 0x09, 0x09, 0x01], // F
+    [0x3E, 0x41, 0x49, 0x49, 0x7A], // G
+    [0x7F, 0x08, 0x08, 0x08, 0x7F], // H
+    [0x00, 0x41, 0x7F, 0x41, 0x00], // I
+    [0x20, 0x40, 0x41, 0x3F, 0x01], // J
+    [0x7F, 0x08, 0x14, 0x22, 0x41], // K
+    [0x7F, 0x40, 0x40, 0x40, 0x40], // L
+    [0x7F, 0x02, 0x0C, 0x02, 0x7F], // M
+    [0x7F, 0x04, 0x08, 0x10, 0x7F], // N
+    [0x3E, 0x41, 0x41, 0x41, 0x3E], // O
+    [0x7F, 0x09, 0x09, 0x09, 0x06], // P
+    [0x3E, 0x41, 0x51, 0x21, 0x5E], // Q
+    [0x7F, 0x09, 0x19, 0x29, 0x46], // R
+    [0x46, 0x49, 0x49, 0x49, 0x31], // S
+    [0x01, 0x01, 0x7F, 0x01, 0x01], // T
+    [0x3F, 0x40, 0x40, 0x40, 0x3F], // U
+    [0x1F, 0x20, 0x40, 0x20, 0x1F], // V
+    [0x3F, 0x40, 0x38, 0x40, 0x3F], // W
+    [0x63, 0x14, 0x08, 0x14, 0x63], // X
+    [0x07, 0x08, 0x70, 0x08, 0x07], // Y
+    [0x61, 0x51, 0x49, 0x45, 0x43], // Z
+    [0x00, 0x7F, 0x41, 0x41, 0x00], // [
+    [0x02, 0x04, 0x08, 0x10, 0x20], // backslash
+    [0x00, 0x41, 0x41, 0x7F, 0x00], // ]
+    [0x04, 0x02, 0x01, 0x02, 0x04], // ^
+    [0x40, 0x40, 0x40, 0x40, 0x40], // _
+    [0x00, 0x01, 0x02, 0x04, 0x00], // `
+    [0x20, 0x54, 0x54, 0x54, 0x78], // a
+    [0x7F, 0x48, 0x44, 0x44, 0x38], // b
+    [0x38, 0x44, 0x44, 0x44, 0x20], // c
+    [0x38, 0x44, 0x44, 0x48, 0x7F], // d
+    [0x38, 0x54, 0x54, 0x54, 0x18], // e
+    [0x08, 0x7E, 0x09, 0x01, 0x02], // f
+    [0x0C, 0x52, 0x52, 0x52, 0x3E], // g
+    [0x7F, 0x08, 0x04, 0x04, 0x78], // h
+    [0x00, 0x44, 0x7D, 0x40, 0x00], // i
+    [0x20, 0x40, 0x44, 0x3D, 0x00], // j
+    [0x7F, 0x10, 0x28, 0x44, 0x00], // k
+    [0x00, 0x41, 0x7F, 0x40, 0x00], // l
+    [0x7C, 0x04, 0x18, 0x04, 0x78], // m
+    [0x7C, 0x08, 0x04, 0x04, 0x78], // n
+    [0x38, 0x44, 0x44, 0x44, 0x38], // o
+    [0x7C, 0x14, 0x14, 0x14, 0x08], // p
+    [0x08, 0x14, 0x14, 0x18, 0x7C], // q
+    [0x7C, 0x08, 0x04, 0x04, 0x08], // r
+    [0x48, 0x54, 0x54, 0x54, 0x20], // s
+    [0x04, 0x3F, 0x44, 0x40, 0x20], // t
+    [0x3C, 0x40, 0x40, 0x20, 0x7C], // u
+    [0x1C, 0x20, 0x40, 0x20, 0x1C], // v
+    [0x3C, 0x40, 0x30, 0x40, 0x3C], // w
+    [0x44, 0x28, 0x10, 0x28, 0x44], // x
+    [0x0C, 0x50, 0x50, 0x50, 0x3C], // y
+    [0x44, 0x64, 0x54, 0x4C, 0x44], // z
+    [0x00, 0x08, 0x36, 0x41, 0x00], // {
+    [0x00, 0x00, 0x7F, 0x00, 0x00], // |
+    [0x00, 0x41, 0x36, 0x08, 0x00], // }
+    [0x08, 0x04, 0x04, 0x08, 0x04], // ~
+];
+
+/// Dark and light modules of the barcode, row by row
+struct Modules {
+    width: usize,
+    rows: Vec<Vec<bool>>,
+
+    /// height of every row, in modules
+    row_height: usize,
+
+    /// light margin around the code, in modules
+    quiet_zone: usize,
+}
+
+impl Modules {
+    fn total_width(&self) -> usize {
+        self.width + 2 * self.quiet_zone
+    }
+
+    fn total_height(&self) -> usize {
+        self.rows.len() * self.row_height + 2 * self.quiet_zone
+    }
+}
+
+impl Barcode {
+    /// Render barcode to SVG, `alt_text` is shown below the code
+    pub fn to_svg(&self) -> PassResult<String> {
+        let modules = self.modules()?;
+        let text_height = if self.alt_text.is_some() {
+            TEXT_HEIGHT
+        } else {
+            0
+        };
+        let width = modules.total_width();
+        let height = modules.total_height() + text_height;
+
+        let mut svg = String::new();
+        // writing into String can't fail
+        let _ = write!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {} {}" shape-rendering="crispEdges">"#,
+            width, height
+        );
+        let _ = write!(
+            svg,
+            r##"<rect width="{}" height="{}" fill="#fff"/><path fill="#000" d=""##,
+            width, height
+        );
+        for (row_index, row) in modules.rows.iter().enumerate() {
+            let y = modules.quiet_zone + row_index * modules.row_height;
+            let mut column = 0;
+            while column < modules.width {
+                if !row[column] {
+                    column += 1;
+                    continue;
+                }
+                let start = column;
+                while column < modules.width && row[column] {
+                    column += 1;
+                }
+                let _ = write!(
+                    svg,
+                    "M{} {}h{}v{}h-{}z",
+                    modules.quiet_zone + start,
+                    y,
+                    column - start,
+                    modules.row_height,
+                    column - start
+                );
+            }
+        }
+        svg.push_str(r#""/>"#);
+
+        if let Some(alt_text) = &self.alt_text {
+            let _ = write!(
+                svg,
+                r#"<text x="{}" y="{}" font-family="sans-serif" font-size="4" text-anchor="middle">{}</text>"#,
+                width / 2,
+                modules.total_height() + 3,
+                escape_html(alt_text)
+            );
+        }
+        svg.push_str("</svg>");
+        Ok(svg)
+    }
+
+    /// Render barcode to grayscale PNG with `scale` pixels per module.
+    /// `alt_text` is drawn below the code, characters outside of ASCII as `?`,
+    /// and also stored in the Description text chunk.
+    pub fn to_png(&self, scale: u32) -> PassResult<Vec<u8>> {
+        let modules = self.modules()?;
+        let scale = scale.max(1) as usize;
+        let width = modules.total_width() * scale;
+        let code_height = modules.total_height() * scale;
+
+        // glyph pixel size, with two pixels between lines of the 7 pixels high glyphs
+        let alt_text = self.alt_text.as_deref().unwrap_or("");
+        let glyph_scale = width
+            .checked_div(alt_text.chars().count() * 6)
+            .map_or(0, |fit| (TEXT_HEIGHT * scale / 9).min(fit).max(1));
+        let text_height = if alt_text.is_empty() {
+            0
+        } else {
+            (TEXT_HEIGHT * scale).max(9 * glyph_scale)
+        };
+        let height = code_height + text_height;
+
+        let mut pixels = vec![0xFF; width * height];
+        for (row_index, row) in modules.rows.iter().enumerate() {
+            let top = (modules.quiet_zone + row_index * modules.row_height) * scale;
+            for (column, _) in row.iter().enumerate().filter(|(_, dark)| **dark) {
+                let left = (modules.quiet_zone + column) * scale;
+                for y in top..top + modules.row_height * scale {
+                    let line = y * width;
+                    for pixel in &mut pixels[line + left..line + left + scale] {
+                        *pixel = 0;
+                    }
+                }
+            }
+        }
+        if !alt_text.is_empty() {
+            let top = code_height + (text_height - 7 * glyph_scale) / 2;
+            draw_text(&mut pixels, width, top, alt_text, glyph_scale);
+        }
+
+        let mut png = Vec::new();
+        let mut encoder = Encoder::new(&mut png, width as u32, height as u32);
+        encoder.set_color(ColorType::Grayscale);
+        encoder.set_depth(BitDepth::Eight);
+        if let Some(alt_text) = &self.alt_text {
+            encoder
+                .add_text_chunk("Description".to_string(), alt_text.clone())
+                .map_err(|source| PassError::EncodePng { source })?;
+        }
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(&pixels))
+            .map_err(|source| PassError::EncodePng { source })?;
+        Ok(png)
+    }
+
+    fn modules(&self) -> PassResult<Modules> {
+        self.validate()?;
+        let message = self.encoded_message()?;
+        match self.format {
+            BarcodeFormat::QR => qr_modules(&message),
+            BarcodeFormat::PDF417 => pdf417_modules(&message),
+            BarcodeFormat::Aztec => aztec_modules(&message),
+            BarcodeFormat::Code128 => Ok(code128_modules(&message)),
+        }
+    }
+}
+
+impl Pass {
+    /// First barcode of the pass which can be rendered, for printed tickets
+    pub fn printable_barcode(&self) -> Option<&Barcode> {
+        self.barcodes()
+            .iter()
+            .find(|barcode| barcode.modules().is_ok())
+    }
+}
+
+fn qr_modules(message: &[u8]) -> PassResult<Modules> {
+    let code = QrCode::with_error_correction_level(message, EcLevel::M)
+        .map_err(|_| PassError::InvalidBarcode("message doesn't fit the barcode format"))?;
+    let width = code.width();
+    let colors = code.to_colors();
+    Ok(Modules {
+        width,
+        rows: colors
+            .chunks(width)
+            .map(|row| row.iter().map(|color| *color == Color::Dark).collect())
+            .collect(),
+        row_height: 1,
+        quiet_zone: 4,
+    })
+}
+
+/// Encode ASCII message with code sets A and B
+fn code128_modules(message: &[u8]) -> Modules {
+    let needs_code_a = |byte: u8| byte < 32;
+    let value_of = |byte: u8| {
+        if needs_code_a(byte) {
+            byte as usize + 64
+        } else {
+            byte as usize - 32
+        }
+    };
+
+    let mut code_a = message.first().is_some_and(|byte| needs_code_a(*byte));
+    let mut symbols = vec![if code_a {
+        CODE128_START_A
+    } else {
+        CODE128_START_B
+    }];
+    for byte in message {
+        // space and digits are the same in both sets, lowercase letters only in B
+        let switch = if code_a {
+            *byte >= 96
+        } else {
+            needs_code_a(*byte)
+        };
+        if switch {
+            symbols.push(if code_a {
+                CODE128_CODE_B
+            } else {
+                CODE128_CODE_A
+            });
+            code_a = !code_a;
+        }
+        symbols.push(value_of(*byte));
+    }
+
+    let checksum = symbols
+        .iter()
+        .enumerate()
+        .map(|(position, symbol)| position.max(1) * symbol)
+        .sum::<usize>()
+        % 103;
+    symbols.push(checksum);
+
+    let mut row = Vec::new();
+    let patterns = symbols
+        .iter()
+        .map(|symbol| CODE128_PATTERNS[*symbol])
+        .chain(Some(CODE128_STOP));
+    for pattern in patterns {
+        for (index, width) in pattern.bytes().enumerate() {
+            let dark = index % 2 == 0;
+            row.extend((0..width - b'0').map(|_| dark));
+        }
+    }
+
+    Modules {
+        width: row.len(),
+        rows: vec![row],
+        row_height: CODE128_HEIGHT,
+        quiet_zone: 10,
+    }
+}
+
+/// Encode message with text or byte compaction, whichever is shorter,
+/// and the error correction level recommended for the count of codewords
+fn pdf417_modules(message: &[u8]) -> PassResult<Modules> {
+    let bytes = pdf417_bytes(message);
+    let mut codewords = match pdf417_text(message) {
+        Some(text) if text.len() <= bytes.len() => text,
+        _ => bytes,
+    };
+    let level = match codewords.len() {
+        0..=40 => 2,
+        41..=160 => 3,
+        161..=320 => 4,
+        321..=863 => 5,
+        _ => {
+            return Err(PassError::InvalidBarcode(
+                "message doesn't fit the barcode format",
+            ))
+        }
+    };
+    let check_count = 2 << level;
+
+    // 3 to 90 rows of 1 to 30 columns, with the width closest to three times the height
+    let (rows, columns) = (1..=30)
+        .map(|columns| {
+            let rows = (codewords.len() + check_count + columns) / columns;
+            (rows, columns)
+        })
+        .filter(|(rows, columns)| (3..=90).contains(rows) && rows * columns - check_count <= 928)
+        .min_by_key(|(rows, columns)| (17 * columns + 69).abs_diff(3 * PDF417_ROW_HEIGHT * rows))
+        .ok_or(PassError::InvalidBarcode(
+            "message doesn't fit the barcode format",
+        ))?;
+
+    // length descriptor counts itself, data and padding
+    let data_count = rows * columns - check_count;
+    codewords.insert(0, data_count);
+    codewords.resize(data_count, 900);
+    let check_words = pdf417_check_words(&codewords, check_count);
+    codewords.extend(check_words);
+
+    // row indicators on the left and right rotate row count, error correction level
+    // and column count by the cluster of the row
+    let row_indicators = [(rows - 1) / 3, level * 3 + (rows - 1) % 3, columns - 1];
+    let rows = codewords
+        .chunks(columns)
+        .enumerate()
+        .map(|(row, data)| {
+            let cluster = row % 3;
+            let patterns = &PDF417_PATTERNS[cluster];
+            let group = row / 3 * 30;
+            let left = group + row_indicators[cluster];
+            let right = group + row_indicators[(cluster + 2) % 3];
+
+            let mut modules = Vec::new();
+            push_bits(&mut modules, PDF417_START, 17);
+            for codeword in Some(left).iter().chain(data).chain(Some(right).iter()) {
+                push_bits(&mut modules, patterns[*codeword] as usize, 17);
+            }
+            push_bits(&mut modules, PDF417_STOP, 18);
+            modules
+        })
+        .collect();
+
+    Ok(Modules {
+        width: 17 * columns + 69,
+        rows,
+        row_height: PDF417_ROW_HEIGHT,
+        quiet_zone: 2,
+    })
+}
+
+/// Text compaction with alpha, lower and mixed submodes and shifts to punctuation,
+/// `None` if message has bytes out of the submodes
+fn pdf417_text(message: &[u8]) -> Option<Vec<usize>> {
+    const ALPHA: usize = 0;
+    const LOWER: usize = 1;
+    const MIXED: usize = 2;
+
+    let mut values = Vec::new();
+    let mut submode = ALPHA;
+    for byte in message.iter().cloned() {
+        if byte == b' ' {
+            values.push(26);
+        } else if byte.is_ascii_uppercase() {
+            match submode {
+                ALPHA => {}
+                // shift, lower submode stays
+                LOWER => values.push(27),
+                _ => {
+                    values.push(28);
+                    submode = ALPHA;
+                }
+            }
+            values.push((byte - b'A') as usize);
+        } else if byte.is_ascii_lowercase() {
+            if submode != LOWER {
+                values.push(27);
+                submode = LOWER;
+            }
+            values.push((byte - b'a') as usize);
+        } else if let Some(value) = PDF417_MIXED.iter().position(|c| *c == byte) {
+            if submode != MIXED {
+                values.push(28);
+                submode = MIXED;
+            }
+            values.push(value);
+        } else {
+            values.push(29);
+            values.push(PDF417_PUNCTUATION.iter().position(|c| *c == byte)?);
+        }
+    }
+
+    // two values in a codeword, odd count is padded with a shift
+    if values.len() % 2 == 1 {
+        values.push(29);
+    }
+    Some(
+        values
+            .chunks(2)
+            .map(|pair| pair[0] * 30 + pair[1])
+            .collect(),
+    )
+}
+
+/// Byte compaction, six bytes to five base 900 codewords, rest of the bytes one by one
+fn pdf417_bytes(message: &[u8]) -> Vec<usize> {
+    let mut groups = message.chunks_exact(6);
+    // latch telling whether the count of bytes is a multiple of six
+    let mut codewords = vec![if groups.remainder().is_empty() {
+        924
+    } else {
+        901
+    }];
+    for group in &mut groups {
+        let mut value = group
+            .iter()
+            .fold(0u64, |value, byte| value << 8 | *byte as u64);
+        let mut digits = [0; 5];
+        for digit in digits.iter_mut().rev() {
+            *digit = (value % 900) as usize;
+            value /= 900;
+        }
+        codewords.extend_from_slice(&digits);
+    }
+    codewords.extend(groups.remainder().iter().map(|byte| *byte as usize));
+    codewords
+}
+
+/// Reed-Solomon check words modulo 929, generator polynomial has roots from 3^1
+fn pdf417_check_words(words: &[usize], count: usize) -> Vec<usize> {
+    let mut generator = vec![1];
+    let mut root = 1;
+    for _ in 0..count {
+        root = root * 3 % 929;
+        let mut next = generator.clone();
+        next.push(0);
+        for (index, coefficient) in generator.iter().enumerate() {
+            next[index + 1] = (next[index + 1] + 929 - coefficient * root % 929) % 929;
+        }
+        generator = next;
+    }
+
+    let mut remainder = vec![0; count];
+    for word in words {
+        let factor = (word + remainder[0]) % 929;
+        remainder.rotate_left(1);
+        remainder[count - 1] = 0;
+        for (check, coefficient) in remainder.iter_mut().zip(&generator[1..]) {
+            *check = (*check + 929 - coefficient * factor % 929) % 929;
+        }
+    }
+    // check words are the negated remainder of the words shifted by the count
+    remainder.iter().map(|check| (929 - check) % 929).collect()
+}
+
+/// Encode message with binary shifts, picking the smallest symbol with at least 33% of check words
+fn aztec_modules(message: &[u8]) -> PassResult<Modules> {
+    let mut bits = Vec::new();
+    for chunk in message.chunks(AZTEC_BINARY_SHIFT_MAX) {
+        // binary shift from the initial upper mode, length over 31 continues in 11 bits
+        push_bits(&mut bits, 31, 5);
+        if chunk.len() <= 31 {
+            push_bits(&mut bits, chunk.len(), 5);
+        } else {
+            push_bits(&mut bits, 0, 5);
+            push_bits(&mut bits, chunk.len() - 31, 11);
+        }
+        for byte in chunk {
+            push_bits(&mut bits, *byte as usize, 8);
+        }
+    }
+
+    // compact symbols with 1 to 4 layers, then full symbols from 4 layers
+    let check_bits = bits.len() * 33 / 100 + 11;
+    let symbol = (0..=32).find_map(|index| {
+        let compact = index <= 3;
+        let layers = if compact { index + 1 } else { index };
+        let total_bits = aztec_layer_bits(layers, compact);
+        if bits.len() + check_bits > total_bits {
+            return None;
+        }
+        let word_size = aztec_word_size(layers);
+        let stuffed = aztec_stuff_bits(&bits, word_size);
+        let usable_bits = total_bits - total_bits % word_size;
+        if compact && stuffed.len() > word_size * 64 || stuffed.len() + check_bits > usable_bits {
+            return None;
+        }
+        Some((compact, layers, word_size, stuffed))
+    });
+    let (compact, layers, word_size, stuffed) = symbol.ok_or(PassError::InvalidBarcode(
+        "message doesn't fit the barcode format",
+    ))?;
+
+    let data_bits = aztec_check_words(&stuffed, aztec_layer_bits(layers, compact), word_size);
+    let mut mode_message = Vec::new();
+    let data_words = stuffed.len() / word_size;
+    if compact {
+        push_bits(&mut mode_message, layers - 1, 2);
+        push_bits(&mut mode_message, data_words - 1, 6);
+    } else {
+        push_bits(&mut mode_message, layers - 1, 5);
+        push_bits(&mut mode_message, data_words - 1, 11);
+    }
+    let mode_message = aztec_check_words(&mode_message, if compact { 28 } else { 40 }, 4);
+
+    // full symbols have reference grid lines every 16 modules from the center
+    let base_size = if compact { 11 } else { 14 } + layers * 4;
+    let size = if compact {
+        base_size
+    } else {
+        base_size + 1 + 2 * ((base_size / 2 - 1) / 15)
+    };
+    let center = size / 2;
+    let alignment: Vec<usize> = if compact {
+        (0..base_size).collect()
+    } else {
+        (0..base_size)
+            .map(|index| {
+                let half = base_size / 2;
+                if index < half {
+                    let offset = half - index - 1;
+                    center - offset - offset / 15 - 1
+                } else {
+                    let offset = index - half;
+                    center + offset + offset / 15 + 1
+                }
+            })
+            .collect()
+    };
+
+    let mut rows = vec![vec![false; size]; size];
+    {
+        let mut set = |x: usize, y: usize| rows[y][x] = true;
+
+        // layers go from outside in, each has a band of two modules on every side
+        let mut offset = 0;
+        for layer in 0..layers {
+            let side = (layers - layer) * 4 + if compact { 9 } else { 12 };
+            let near = |step: usize| alignment[layer * 2 + step];
+            let far = |step: usize| alignment[base_size - 1 - layer * 2 - step];
+            for step in 0..side {
+                for bit in 0..2 {
+                    let index = offset + step * 2 + bit;
+                    if data_bits[index] {
+                        set(near(bit), near(step));
+                    }
+                    if data_bits[index + side * 2] {
+                        set(near(step), far(bit));
+                    }
+                    if data_bits[index + side * 4] {
+                        set(far(bit), far(step));
+                    }
+                    if data_bits[index + side * 6] {
+                        set(far(step), near(bit));
+                    }
+                }
+            }
+            offset += side * 8;
+        }
+
+        if compact {
+            for index in 0..7 {
+                let position = center - 3 + index;
+                if mode_message[index] {
+                    set(position, center - 5);
+                }
+                if mode_message[index + 7] {
+                    set(center + 5, position);
+                }
+                if mode_message[20 - index] {
+                    set(position, center + 5);
+                }
+                if mode_message[27 - index] {
+                    set(center - 5, position);
+                }
+            }
+        } else {
+            for index in 0..10 {
+                let position = center - 5 + index + index / 5;
+                if mode_message[index] {
+                    set(position, center - 7);
+                }
+                if mode_message[index + 10] {
+                    set(center + 7, position);
+                }
+                if mode_message[29 - index] {
+                    set(position, center + 7);
+                }
+                if mode_message[39 - index] {
+                    set(center - 7, position);
+                }
+            }
+            let mut line = 0;
+            while line * 15 < base_size / 2 - 1 {
+                let distance = line * 16;
+                for position in (center & 1..size).step_by(2) {
+                    set(center - distance, position);
+                    set(center + distance, position);
+                    set(position, center - distance);
+                    set(position, center + distance);
+                }
+                line += 1;
+            }
+        }
+
+        // bullseye of dark rings and orientation marks in the corners of the mode message
+        let radius = if compact { 5 } else { 7 };
+        for ring in (0..radius).step_by(2) {
+            for position in center - ring..=center + ring {
+                set(position, center - ring);
+                set(position, center + ring);
+                set(center - ring, position);
+                set(center + ring, position);
+            }
+        }
+        set(center - radius, center - radius);
+        set(center - radius + 1, center - radius);
+        set(center - radius, center - radius + 1);
+        set(center + radius, center - radius);
+        set(center + radius, center - radius + 1);
+        set(center + radius, center + radius - 1);
+    }
+
+    Ok(Modules {
+        width: size,
+        rows,
+        row_height: 1,
+        quiet_zone: 2,
+    })
+}
+
+/// Bits in the data layers of the symbol
+fn aztec_layer_bits(layers: usize, compact: bool) -> usize {
+    (if compact { 88 } else { 112 } + 16 * layers) * layers
+}
+
+fn aztec_word_size(layers: usize) -> usize {
+    match layers {
+        1..=2 => 6,
+        3..=8 => 8,
+        9..=22 => 10,
+        _ => 12,
+    }
+}
+
+/// Split bits to words, words of all zeros or all ones are reserved,
+/// so their last bit is replaced by the complement and moved to the next word
+fn aztec_stuff_bits(bits: &[bool], word_size: usize) -> Vec<bool> {
+    let mask = (1 << word_size) - 2;
+    let mut stuffed = Vec::new();
+    let mut index = 0;
+    while index < bits.len() {
+        // last word is padded with ones
+        let word = (index..index + word_size).fold(0, |word, bit| {
+            word << 1 | bits.get(bit).map_or(1, |bit| *bit as usize)
+        });
+        if word & mask == mask {
+            push_bits(&mut stuffed, word & mask, word_size);
+            index += word_size - 1;
+        } else if word & mask == 0 {
+            push_bits(&mut stuffed, word | 1, word_size);
+            index += word_size - 1;
+        } else {
+            push_bits(&mut stuffed, word, word_size);
+            index += word_size;
+        }
+    }
+    stuffed
+}
+
+/// Append Reed-Solomon check words filling `total_bits`, unused bits go first
+fn aztec_check_words(bits: &[bool], total_bits: usize, word_size: usize) -> Vec<bool> {
+    let mut words: Vec<usize> = bits
+        .chunks(word_size)
+        .map(|word| word.iter().fold(0, |word, bit| word << 1 | *bit as usize))
+        .collect();
+    let check_words = reed_solomon(&words, total_bits / word_size - words.len(), word_size);
+    words.extend(check_words);
+
+    let mut checked = vec![false; total_bits % word_size];
+    for word in words {
+        push_bits(&mut checked, word, word_size);
+    }
+    checked
+}
+
+/// Reed-Solomon check words in the Galois field of the Aztec word size,
+/// generator polynomial has roots from a^1
+fn reed_solomon(words: &[usize], count: usize, word_size: usize) -> Vec<usize> {
+    let field = GaloisField::new(word_size);
+    let mut generator = vec![1];
+    for power in 1..=count {
+        let root = field.exp(power);
+        let mut next = generator.clone();
+        next.push(0);
+        for (index, coefficient) in generator.iter().enumerate() {
+            next[index + 1] ^= field.mul(*coefficient, root);
+        }
+        generator = next;
+    }
+
+    let mut remainder = vec![0; count];
+    for word in words {
+        let factor = word ^ remainder.first().cloned().unwrap_or(0);
+        remainder.rotate_left(1);
+        if let Some(last) = remainder.last_mut() {
+            *last = 0;
+        }
+        for (check, coefficient) in remainder.iter_mut().zip(&generator[1..]) {
+            *check ^= field.mul(*coefficient, factor);
+        }
+    }
+    remainder
+}
+
+/// GF(2^word_size) with the primitive polynomials of Aztec
+struct GaloisField {
+    exp: Vec<usize>,
+    log: Vec<usize>,
+}
+
+impl GaloisField {
+    fn new(word_size: usize) -> GaloisField {
+        let polynomial = match word_size {
+            4 => 0x13,
+            6 => 0x43,
+            8 => 0x12D,
+            10 => 0x409,
+            _ => 0x1069,
+        };
+        let size = 1 << word_size;
+        let mut exp = vec![0; size - 1];
+        let mut log = vec![0; size];
+        let mut value = 1;
+        for (power, exp) in exp.iter_mut().enumerate() {
+            *exp = value;
+            log[value] = power;
+            value <<= 1;
+            if value >= size {
+                value ^= polynomial;
+            }
+        }
+        GaloisField { exp, log }
+    }
+
+    fn exp(&self, power: usize) -> usize {
+        self.exp[power % self.exp.len()]
+    }
+
+    fn mul(&self, a: usize, b: usize) -> usize {
+        if a == 0 || b == 0 {
+            0
+        } else {
+            self.exp(self.log[a] + self.log[b])
+        }
+    }
+}
+
+/// Append `count` lowest bits of value, most significant first
+fn push_bits(bits: &mut Vec<bool>, value: usize, count: usize) {
+    bits.extend((0..count).rev().map(|bit| value >> bit & 1 == 1));
+}
+
+/// Draw text centered on the line starting at `top`, with glyph pixels of `scale` size
+fn draw_text(pixels: &mut [u8], width: usize, top: usize, text: &str, scale: usize) {
+    let text_width = (text.chars().count() * 6).saturating_sub(1) * scale;
+    let mut left = width.saturating_sub(text_width) / 2;
+    for char in text.chars() {
+        let index = match char {
+            ' '..='~' => char as usize - 32,
+            _ => '?' as usize - 32,
+        };
+        for (column, bits) in FONT[index].iter().enumerate() {
+            for row in (0..7).filter(|row| bits >> row & 1 == 1) {
+                let x = left + column * scale;
+                for y in top + row * scale..top + (row + 1) * scale {
+                    let line = y * width;
+                    for pixel in pixels[line..line + width].iter_mut().skip(x).take(scale) {
+                        *pixel = 0;
+                    }
+                }
+            }
+        }
+        left += 6 * scale;
+    }
+}
+
+mod test {
+    #[test]
+    fn code128_patterns() {
+        use super::*;
+
+        for pattern in CODE128_PATTERNS.iter() {
+            let widths: Vec<u8> = pattern.bytes().map(|width| width - b'0').collect();
+            assert_eq!(widths.iter().sum::<u8>(), 11, "{}", pattern);
+            let bars: u8 = widths.iter().step_by(2).sum();
+            assert_eq!(bars % 2, 0, "{}", pattern);
+        }
+        let mut unique = CODE128_PATTERNS.to_vec();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(unique.len(), CODE128_PATTERNS.len());
+
+        // start B, "P", "J", checksum (104 + 48 + 2 * 42) % 103 = 30, stop
+        let modules = code128_modules(b"PJ");
+        assert_eq!(modules.width, 5 * 11 + 2);
+        let expected: Vec<bool> = ["211214", "313121", "112133", "212123", "2331112"]
+            .iter()
+            .flat_map(|pattern| pattern.bytes().enumerate())
+            .flat_map(|(index, width)| (0..width - b'0').map(move |_| index % 2 == 0))
+            .collect();
+        assert_eq!(modules.rows[0], expected);
+    }
+
+    #[test]
+    fn render_svg_and_png() {
+        use super::*;
+
+        let mut barcode = Barcode::from((BarcodeFormat::QR, "SFOJFK JOHN APPLESEED"));
+        barcode.alt_text = Some("Seat <12A>".into());
+        let svg = barcode.to_svg().unwrap();
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("Seat &lt;12A&gt;"));
+
+        let png = barcode.to_png(4).unwrap();
+        assert_eq!(&png[1..4], b"PNG");
+        let height = |png: &[u8]| u32::from_be_bytes([png[20], png[21], png[22], png[23]]);
+        barcode.alt_text = None;
+        let without_text = barcode.to_png(4).unwrap();
+        assert_eq!(height(&png), height(&without_text) + 4 * TEXT_HEIGHT as u32);
+
+        let code128 = Barcode::from((BarcodeFormat::Code128, "0001"));
+        assert!(code128.to_svg().unwrap().contains("v50"));
+
+        let aztec = Barcode::from((BarcodeFormat::Aztec, "0001"));
+        assert!(aztec.to_png(1).is_ok());
+        // one column of 12 rows, 3 modules high, with 2 modules of quiet zone
+        let pdf417 = Barcode::from((BarcodeFormat::PDF417, "0001"));
+        assert_eq!(height(&pdf417.to_png(2).unwrap()), 2 * (12 * 3 + 4));
+        assert!(pdf417.to_svg().unwrap().contains("v3"));
+    }
+
+    #[test]
+    fn aztec_symbols() {
+        use super::*;
+
+        // compact symbol with one layer has bullseye with orientation marks in the corners
+        let compact = aztec_modules(b"0001").unwrap();
+        assert_eq!(compact.width, 15);
+        let row = |modules: &Modules, y: usize, x: std::ops::Range<usize>| -> String {
+            x.map(|x| if modules.rows[y][x] { '#' } else { '.' })
+                .collect()
+        };
+        assert_eq!(row(&compact, 7, 3..12), "#.#.#.#.#");
+        assert_eq!(row(&compact, 3, 3..12), "#########");
+        assert_eq!(row(&compact, 4, 3..12), "#.......#");
+        let dark = |x: usize, y: usize| compact.rows[y][x];
+        assert!(dark(2, 2) && dark(3, 2) && dark(2, 3));
+        assert!(dark(12, 2) && dark(12, 3) && !dark(11, 2));
+        assert!(dark(12, 11) && !dark(12, 12) && !dark(11, 12));
+        assert!(!dark(2, 12) && !dark(3, 12) && !dark(2, 11));
+
+        // full symbol with 12 layers has reference grid lines 16 modules from the center
+        let full = aztec_modules(&[b'A'; 300]).unwrap();
+        assert_eq!(full.width, 67);
+        let grid: String = (1..67)
+            .step_by(2)
+            .map(|x| if full.rows[33 - 16][x] { '#' } else { '.' })
+            .collect();
+        assert_eq!(grid, "#".repeat(33));
+
+        // zeros need stuffing in every word, the worst case of the capacity
+        let capacity = BarcodeFormat::Aztec.capacity();
+        assert_eq!(aztec_modules(&vec![0; capacity]).unwrap().width, 151);
+        assert!(aztec_modules(&vec![0; capacity + 1]).is_err());
+    }
+
+    #[test]
+    fn pdf417_symbols() {
+        use super::*;
+
+        // latch to mixed, "00", "01" and a shift padding the odd count
+        assert_eq!(pdf417_text(b"0001"), Some(vec![840, 0, 59]));
+        // "A", latch to lower, "b", shift to punctuation, "!" and padding
+        assert_eq!(pdf417_text(b"Ab!"), Some(vec![27, 59, 329]));
+        assert_eq!(pdf417_text("Café".as_bytes()), None);
+        assert_eq!(
+            pdf417_bytes(&[1, 2, 3, 4, 5, 6, 7]),
+            vec![901, 1, 620, 89, 74, 846, 7]
+        );
+        assert_eq!(pdf417_bytes(&[0; 6])[0], 924);
+
+        // length descriptor and 3 data codewords followed by 8 check words in one column
+        let modules = pdf417_modules(b"0001").unwrap();
+        assert_eq!(modules.width, 17 + 69);
+        assert_eq!(modules.rows.len(), 12);
+        let codeword = |row: usize, column: usize| {
+            let pattern = modules.rows[row][17 * (column + 1)..17 * (column + 2)]
+                .iter()
+                .fold(0, |pattern, dark| pattern << 1 | *dark as u32);
+            PDF417_PATTERNS[row % 3]
+                .iter()
+                .position(|candidate| *candidate == pattern)
+        };
+        let data: Vec<_> = (0..4).map(|row| codeword(row, 1)).collect();
+        assert_eq!(data, vec![Some(4), Some(840), Some(0), Some(59)]);
+
+        // row indicators of 12 rows, error correction level 2 and one column
+        assert_eq!((codeword(0, 0), codeword(0, 2)), (Some(3), Some(0)));
+        assert_eq!((codeword(1, 0), codeword(1, 2)), (Some(8), Some(3)));
+        assert_eq!((codeword(2, 0), codeword(2, 2)), (Some(0), Some(8)));
+        for row in &modules.rows {
+            let mut start = Vec::new();
+            push_bits(&mut start, PDF417_START, 17);
+            assert_eq!(&row[..17], &start[..]);
+            assert_eq!(row.len(), modules.width);
+        }
+
+        let capacity = BarcodeFormat::PDF417.capacity();
+        assert_eq!(pdf417_modules(&vec![0; capacity]).unwrap().rows.len(), 47);
+        assert!(pdf417_modules(&vec![0; capacity + 1]).is_err());
+    }
+
+    #[test]
+    fn reed_solomon_check_words() {
+        use super::*;
+
+        for word_size in [4, 6, 8, 10, 12].iter().cloned() {
+            let field = GaloisField::new(word_size);
+            let data: Vec<usize> = (1..20).map(|word| word * 7 % (1 << word_size)).collect();
+            let mut words = data.clone();
+            words.extend(reed_solomon(&data, 5, word_size));
+            for power in 1..=5 {
+                let root = field.exp(power);
+                let syndrome = words
+                    .iter()
+                    .fold(0, |value, word| field.mul(value, root) ^ word);
+                assert_eq!(syndrome, 0, "word size {}", word_size);
+            }
+        }
+
+        let data: Vec<usize> = (1..20).map(|word| word * 37 % 929).collect();
+        let mut words = data.clone();
+        words.extend(pdf417_check_words(&data, 16));
+        let mut root = 1;
+        for _ in 1..=16 {
+            root = root * 3 % 929;
+            let syndrome = words
+                .iter()
+                .fold(0, |value, word| (value * root + word) % 929);
+            assert_eq!(syndrome, 0);
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use openssl::pkcs7::{Pkcs7, Pkcs7Flags};
+use openssl::pkey::{PKey, Private};
+use openssl::stack::Stack;
+use openssl::x509::X509;
+use std::fmt;
+use {PassError, PassResult};
+
+/// Signing identity used to create `signature` of the manifest.json
+pub struct Signer {
+    /// Pass Type ID certificate, issued by Apple
+    certificate: X509,
+
+    /// Private key of the pass type certificate
+    private_key: PKey<Private>,
+
+    /// Apple Worldwide Developer Relations intermediate certificate
+    wwdr_certificate: X509,
+
+    /// include signed attributes with signing time into signature
+    signing_time: bool,
+}
+
+impl Signer {
+    pub fn new(certificate: X509, private_key: PKey<Private>, wwdr_certificate: X509) -> Signer {
+        Signer {
+            certificate,
+            private_key,
+            wwdr_certificate,
+            signing_time: true,
+        }
+    }
+
+    /// Omit signed attributes with signing time, so signature of the same manifest
+    /// doesn't change between builds. Only RSA keys produce identical signatures.
+    pub fn without_signing_time(mut self) -> Signer {
+        self.signing_time = false;
+        self
+    }
+
+    /// Load signing identity from PEM encoded certificates and private key
+    pub fn from_pem(
+        certificate: &[u8],
+        private_key: &[u8],
+        wwdr_certificate: &[u8],
+    ) -> PassResult<Signer> {
+        let certificate =
+            X509::from_pem(certificate).map_err(|source| PassError::Certificate { source })?;
+        let private_key = PKey::private_key_from_pem(private_key)
+            .map_err(|source| PassError::Certificate { source })?;
+        let wwdr_certificate =
+            X509::from_pem(wwdr_certificate).map_err(|source| PassError::Certificate { source })?;
+
+        Ok(Signer::new(certificate, private_key, wwdr_certificate))
+    }
+
+    /// Create detached PKCS #7 signature in DER format,
+    /// used for manifest.json and personalization token
+    pub fn sign(&self, manifest: &[u8]) -> PassResult<Vec<u8>> {
+        let mut chain = Stack::new().map_err(|source| PassError::Sign { source })?;
+        chain
+            .push(self.wwdr_certificate.clone())
+            .map_err(|source| PassError::Sign { source })?;
+
+        let mut flags = Pkcs7Flags::DETACHED | Pkcs7Flags::BINARY;
+        if !self.signing_time {
+            flags |= Pkcs7Flags::NOATTR;
+        }
+        Pkcs7::sign(
+            &self.certificate,
+            &self.private_key,
+            &chain,
+            manifest,
+            flags,
+        )
+        .and_then(|signature| signature.to_der())
+        .map_err(|source| PassError::Sign { source })
+    }
+}
+
+impl fmt::Debug for Signer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Signer")
+            .field("certificate", &self.certificate.subject_name())
+            .finish()
+    }
+}
+
+pub(crate) mod test {
+    #[allow(dead_code)]
+    pub fn self_signed() -> super::Signer {
+        use openssl::asn1::Asn1Time;
+        use openssl::ec::{EcGroup, EcKey};
+        use openssl::hash::MessageDigest;
+        use openssl::nid::Nid;
+        use openssl::pkey::PKey;
+        use openssl::x509::{X509Builder, X509NameBuilder};
+
+        let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+        let key = PKey::from_ec_key(EcKey::generate(&group).unwrap()).unwrap();
+
+        let mut name = X509NameBuilder::new().unwrap();
+        name.append_entry_by_text("CN", "Pass Type ID: pass.com.example")
+            .unwrap();
+        let name = name.build();
+
+        let mut builder = X509Builder::new().unwrap();
+        builder.set_version(2).unwrap();
+        builder.set_subject_name(&name).unwrap();
+        builder.set_issuer_name(&name).unwrap();
+        builder.set_pubkey(&key).unwrap();
+        builder
+            .set_not_before(&Asn1Time::days_from_now(0).unwrap())
+            .unwrap();
+        builder
+            .set_not_after(&Asn1Time::days_from_now(1).unwrap())
+            .unwrap();
+        builder.sign(&key, MessageDigest::sha256()).unwrap();
+        let certificate = builder.build();
+
+        super::Signer::new(certificate.clone(), key, certificate)
+    }
+
+    #[test]
+    fn sign_manifest() {
+        use openssl::pkcs7::{Pkcs7, Pkcs7Flags};
+        use openssl::stack::Stack;
+        use openssl::x509::store::X509StoreBuilder;
+
+        let signer = self_signed();
+        let manifest = br#"{"pass.json":"da39a3ee5e6b4b0d3255bfef95601890afd80709"}"#;
+        let signature = signer.sign(manifest).unwrap();
+
+        let pkcs7 = Pkcs7::from_der(&signature).unwrap();
+        let store = X509StoreBuilder::new().unwrap().build();
+        let certs = Stack::new().unwrap();
+        pkcs7
+            .verify(&certs, &store, Some(manifest), None, Pkcs7Flags::NOVERIFY)
+            .unwrap();
+    }
+}
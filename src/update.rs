@@ -0,0 +1,276 @@
+use std::error::Error;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use {Pass, PassDiff, PassError, PassResult, PassSource, PreparedAssets, Signer};
+
+/// Issued pass as kept by the web service
+#[derive(Debug, Clone)]
+pub struct StoredPass {
+    pub pass: Pass,
+
+    /// Seconds since Unix epoch of the last change,
+    /// used as `lastUpdated` tag and `Last-Modified` header of the web service
+    pub update_tag: u64,
+
+    /// Signed .pkpass served to devices
+    pub pkpass: Vec<u8>,
+}
+
+impl StoredPass {
+    pub fn last_modified(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(self.update_tag)
+    }
+}
+
+/// Storage of issued passes and device registrations
+pub trait PassStore {
+    fn load(
+        &mut self,
+        pass_type_identifier: &str,
+        serial_number: &str,
+    ) -> Result<Option<StoredPass>, Box<dyn Error + Send + Sync>>;
+
+    fn save(&mut self, stored: StoredPass) -> Result<(), Box<dyn Error + Send + Sync>>;
+
+    /// Push tokens of devices registered for updates of the pass
+    fn push_tokens(
+        &mut self,
+        pass_type_identifier: &str,
+        serial_number: &str,
+    ) -> Result<Vec<String>, Box<dyn Error + Send + Sync>>;
+}
+
+/// Queue of APNs notifications, sent with the pass type certificate and empty payload
+pub trait PushQueue {
+    fn enqueue(&mut self, push_token: &str, pass_type_identifier: &str);
+}
+
+/// Result of the pass update
+#[derive(Debug, Clone)]
+pub struct PassUpdate {
+    /// Changes applied to the pass, nothing is rebuilt and pushed when empty
+    pub diff: PassDiff,
+
+    pub update_tag: u64,
+
+    /// Count of devices notified about the update
+    pub notified: usize,
+}
+
+/// Applies changes to stored passes, rebuilds them and notifies registered devices
+#[derive(Debug)]
+pub struct PassUpdater<S, Q> {
+    store: S,
+    queue: Q,
+    assets: Arc<PreparedAssets>,
+    signer: Arc<Signer>,
+}
+
+impl<S: PassStore, Q: PushQueue> PassUpdater<S, Q> {
+    pub fn new(
+        store: S,
+        queue: Q,
+        assets: Arc<PreparedAssets>,
+        signer: Arc<Signer>,
+    ) -> PassUpdater<S, Q> {
+        PassUpdater {
+            store,
+            queue,
+            assets,
+            signer,
+        }
+    }
+
+    pub fn store(&mut self) -> &mut S {
+        &mut self.store
+    }
+
+    pub fn queue(&mut self) -> &mut Q {
+        &mut self.queue
+    }
+
+    /// Sign and store newly issued pass
+    pub fn issue(&mut self, pass: Pass) -> PassResult<StoredPass> {
+        let stored = StoredPass {
+            pkpass: self.build(pass.clone())?,
+            pass,
+            update_tag: next_update_tag(0),
+        };
+        self.store.save(stored.clone()).map_err(store_error)?;
+        Ok(stored)
+    }
+
+    /// Change stored pass with the closure, which can't change the pass identifiers.
+    /// When anything changed, pass gets new update tag, rebuilt and pushed to registered devices.
+    pub fn update<F>(
+        &mut self,
+        pass_type_identifier: &str,
+        serial_number: &str,
+        mutate: F,
+    ) -> PassResult<PassUpdate>
+    where
+        F: FnOnce(&mut Pass),
+    {
+        let mut stored = self
+            .store
+            .load(pass_type_identifier, serial_number)
+            .map_err(store_error)?
+            .ok_or_else(|| PassError::PassNotFound(serial_number.to_string()))?;
+
+        let mut pass = stored.pass.clone();
+        mutate(&mut pass);
+        if pass.pass_type_identifier != stored.pass.pass_type_identifier
+            || pass.serial_number != stored.pass.serial_number
+        {
+            return Err(PassError::InvalidPass(vec![
+                "update can't change passTypeIdentifier or serialNumber".to_string(),
+            ]));
+        }
+        let diff = stored.pass.diff(&pass);
+        if diff.is_empty() {
+            return Ok(PassUpdate {
+                diff,
+                update_tag: stored.update_tag,
+                notified: 0,
+            });
+        }
+
+        stored.pkpass = self.build(pass.clone())?;
+        stored.pass = pass;
+        stored.update_tag = next_update_tag(stored.update_tag);
+        let update_tag = stored.update_tag;
+        self.store.save(stored).map_err(store_error)?;
+
+        let push_tokens = self
+            .store
+            .push_tokens(pass_type_identifier, serial_number)
+            .map_err(store_error)?;
+        for push_token in &push_tokens {
+            self.queue.enqueue(push_token, pass_type_identifier);
+        }
+
+        Ok(PassUpdate {
+            diff,
+            update_tag,
+            notified: push_tokens.len(),
+        })
+    }
+
+    fn build(&self, pass: Pass) -> PassResult<Vec<u8>> {
+        let mut source = PassSource::with_assets(self.assets.clone());
+        source.add_pass(pass).sign_with(self.signer.clone());
+        source.build_pkpass()
+    }
+}
+
+/// Current time, but always after the previous tag, so devices see every update
+fn next_update_tag(previous: u64) -> u64 {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    now.max(previous + 1)
+}
+
+fn store_error(source: Box<dyn Error + Send + Sync>) -> PassError {
+    PassError::Store { source }
+}
+
+mod test {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[allow(dead_code)]
+    #[derive(Default)]
+    struct MemoryStore {
+        passes: HashMap<String, StoredPass>,
+        devices: Vec<String>,
+    }
+
+    impl PassStore for MemoryStore {
+        fn load(
+            &mut self,
+            _: &str,
+            serial_number: &str,
+        ) -> Result<Option<StoredPass>, Box<dyn Error + Send + Sync>> {
+            Ok(self.passes.get(serial_number).cloned())
+        }
+
+        fn save(&mut self, stored: StoredPass) -> Result<(), Box<dyn Error + Send + Sync>> {
+            self.passes
+                .insert(stored.pass.serial_number.clone(), stored);
+            Ok(())
+        }
+
+        fn push_tokens(
+            &mut self,
+            _: &str,
+            _: &str,
+        ) -> Result<Vec<String>, Box<dyn Error + Send + Sync>> {
+            Ok(self.devices.clone())
+        }
+    }
+
+    #[allow(dead_code)]
+    #[derive(Default)]
+    struct MemoryQueue {
+        push_tokens: Vec<String>,
+    }
+
+    impl PushQueue for MemoryQueue {
+        fn enqueue(&mut self, push_token: &str, _: &str) {
+            self.push_tokens.push(push_token.to_string());
+        }
+    }
+
+    #[test]
+    fn update_and_notify() {
+        use sign::test::self_signed;
+        use {Field, PassBuilder, TransitType};
+
+        let source = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/BoardingPass.pass");
+        let assets = Arc::new(PreparedAssets::prepare(source).unwrap());
+        let store = MemoryStore {
+            devices: vec!["device-token".to_string()],
+            ..Default::default()
+        };
+        let mut updater = PassUpdater::new(
+            store,
+            MemoryQueue::default(),
+            assets,
+            Arc::new(self_signed()),
+        );
+
+        let pass = PassBuilder::new("0001", "pass.com.example", "CDHE9L6U22")
+            .add_header_field(Field::new("GATE", "gate", "23", "Gate changed to %@."))
+            .finish_boarding_pass(TransitType::Air);
+        let issued = updater.issue(pass).unwrap();
+
+        let unchanged = updater.update("pass.com.example", "0001", |_| {}).unwrap();
+        assert_eq!(unchanged.update_tag, issued.update_tag);
+        assert_eq!(unchanged.notified, 0);
+
+        let update = updater
+            .update("pass.com.example", "0001", |pass| pass.voided = true)
+            .unwrap();
+        assert!(update.update_tag > issued.update_tag);
+        assert_eq!(update.diff.voided, Some(true));
+        assert_eq!(update.notified, 1);
+        assert_eq!(
+            updater.queue().push_tokens,
+            vec!["device-token".to_string()]
+        );
+        assert!(updater.store().passes["0001"].pass.voided);
+
+        let renamed = updater.update("pass.com.example", "0001", |pass| {
+            pass.serial_number = "0003".into()
+        });
+        assert!(matches!(renamed, Err(PassError::InvalidPass(_))));
+        assert!(!updater.store().passes.contains_key("0003"));
+
+        match updater.update("pass.com.example", "0002", |_| {}) {
+            Err(PassError::PassNotFound(serial)) => assert_eq!(serial, "0002"),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+}
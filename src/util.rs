@@ -1,8 +1,79 @@
+use serde::Serializer;
 use serde_derive::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 pub fn is_false(value: &bool) -> bool {
     *value == false
 }
 
+/// Serialize map with sorted keys, so pass.json doesn't change between builds
+pub fn serialize_sorted<S: Serializer>(
+    map: &HashMap<String, String>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let sorted: BTreeMap<_, _> = map.iter().collect();
+    serde::Serialize::serialize(&sorted, serializer)
+}
+
+/// Escape text for HTML and XML content and quoted attribute values
+pub(crate) fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('\'', "&#39;")
+        .replace('"', "&quot;")
+}
+
+/// Red, green and blue of color in `rgb(23, 187, 82)` format
+pub fn rgb_components(color: &str) -> Option<[u8; 3]> {
+    let components: Vec<u8> = color
+        .trim()
+        .strip_prefix("rgb(")?
+        .strip_suffix(')')?
+        .split(',')
+        .map(|component| component.trim().parse().ok())
+        .collect::<Option<_>>()?;
+    match components.as_slice() {
+        [red, green, blue] => Some([*red, *green, *blue]),
+        _ => None,
+    }
+}
+
+/// W3C date with time in UTC, e.g. `2026-10-18T14:05:00Z`
+pub fn w3c_date(time: SystemTime) -> String {
+    let seconds = time
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    let (days, time_of_day) = (seconds / 86400, seconds % 86400);
+
+    // civil date from days since 1970-01-01, eras of 400 years start at 0000-03-01
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        time_of_day / 3600,
+        time_of_day % 3600 / 60,
+        time_of_day % 60
+    )
+}
+
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct Empty {}
@@ -0,0 +1,307 @@
+use openssl::base64;
+use openssl::bn::{BigNum, BigNumContext};
+use openssl::derive::Deriver;
+use openssl::ec::{EcGroup, EcKey, EcPoint};
+use openssl::error::ErrorStack;
+use openssl::nid::Nid;
+use openssl::pkey::{HasParams, HasPublic, Id, PKey, Private, Public};
+use openssl::sha::{sha256, Sha256};
+use openssl::symm::{self, Cipher};
+use pass::NFC;
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use {PassError, PassResult};
+
+/// VAS timestamps count seconds since 2001-01-01
+const APPLE_EPOCH: u64 = 978_307_200;
+
+/// Info of the X9.63 key derivation, followed by the ephemeral public key
+const SHARED_INFO: &[u8] = b"\x0did-aes256-GCMApplePay encrypted VAS data";
+
+const KEY_ID_LEN: usize = 4;
+const COORDINATE_LEN: usize = 32;
+const TAG_LEN: usize = 16;
+const IV: [u8; 16] = [0; 16];
+
+/// Merchant key pair of the Value Added Services protocol, used by NFC passes
+pub struct VasKey {
+    private_key: PKey<Private>,
+}
+
+impl VasKey {
+    /// Generate new P-256 key pair
+    pub fn generate() -> PassResult<VasKey> {
+        let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).map_err(vas_key_error)?;
+        let private_key = EcKey::generate(&group)
+            .and_then(PKey::from_ec_key)
+            .map_err(vas_key_error)?;
+        Ok(VasKey { private_key })
+    }
+
+    /// Load existing private key in PEM format, either SEC1 or PKCS #8
+    pub fn from_pem(pem: &[u8]) -> PassResult<VasKey> {
+        let private_key = PKey::private_key_from_pem(pem).map_err(vas_key_error)?;
+        check_curve(&private_key)?;
+        Ok(VasKey { private_key })
+    }
+
+    /// Private key in PKCS #8 PEM format, to be stored on the terminal side
+    pub fn private_key_pem(&self) -> PassResult<Vec<u8>> {
+        self.private_key
+            .private_key_to_pem_pkcs8()
+            .map_err(vas_key_error)
+    }
+
+    /// Base64 encoded X.509 SubjectPublicKeyInfo, the value for `NFC::encryption_public_key`
+    pub fn public_key(&self) -> PassResult<String> {
+        let der = self
+            .private_key
+            .public_key_to_der()
+            .map_err(vas_key_error)?;
+        Ok(base64::encode_block(&der))
+    }
+
+    /// Identifier the device sends with the payload:
+    /// first bytes of SHA-256 of the public key X coordinate
+    pub fn key_id(&self) -> PassResult<[u8; 4]> {
+        key_id(&self.private_key).map_err(vas_key_error)
+    }
+
+    /// Decrypt VAS payload sent by the device, the way a terminal does
+    pub fn decrypt(&self, payload: &[u8]) -> PassResult<VasPayload> {
+        if payload.len() < KEY_ID_LEN + COORDINATE_LEN + TAG_LEN {
+            return Err(PassError::InvalidVasPayload("payload is too short"));
+        }
+        let (key_id, rest) = payload.split_at(KEY_ID_LEN);
+        let (ephemeral_x, encrypted) = rest.split_at(COORDINATE_LEN);
+        let (ciphertext, tag) = encrypted.split_at(encrypted.len() - TAG_LEN);
+        if key_id != self.key_id()? {
+            return Err(PassError::InvalidVasPayload(
+                "payload is encrypted for another key",
+            ));
+        }
+
+        // both points with this X give shared secrets with the same X coordinate,
+        // so the sign of Y doesn't matter
+        let ephemeral = ephemeral_public_key(ephemeral_x)
+            .map_err(|_| PassError::InvalidVasPayload("invalid ephemeral public key"))?;
+        let shared_secret = shared_secret(&self.private_key, &ephemeral).map_err(vas_key_error)?;
+        let key = derive_key(&shared_secret, ephemeral_x);
+        let decrypted =
+            symm::decrypt_aead(Cipher::aes_256_gcm(), &key, Some(&IV), &[], ciphertext, tag)
+                .map_err(|_| PassError::InvalidVasPayload("payload can't be decrypted"))?;
+
+        VasPayload::from_decrypted(decrypted)
+    }
+}
+
+/// Content of the decrypted VAS payload
+#[derive(Debug, Clone, PartialEq)]
+pub struct VasPayload {
+    /// Time the device produced the payload
+    pub timestamp: SystemTime,
+
+    /// `NFC::message` of the pass
+    pub message: String,
+}
+
+impl VasPayload {
+    fn from_decrypted(mut decrypted: Vec<u8>) -> PassResult<VasPayload> {
+        if decrypted.len() < 4 {
+            return Err(PassError::InvalidVasPayload("timestamp is missing"));
+        }
+        let message = decrypted.split_off(4);
+        let seconds = u32::from_be_bytes([decrypted[0], decrypted[1], decrypted[2], decrypted[3]]);
+        let message = String::from_utf8(message)
+            .map_err(|_| PassError::InvalidVasPayload("message is not UTF-8"))?;
+        Ok(VasPayload {
+            timestamp: UNIX_EPOCH + Duration::from_secs(APPLE_EPOCH + u64::from(seconds)),
+            message,
+        })
+    }
+}
+
+/// Encrypt NFC message as the device does, to test terminal side without one
+pub fn encrypt_vas_payload(
+    encryption_public_key: &str,
+    message: &str,
+    timestamp: SystemTime,
+) -> PassResult<Vec<u8>> {
+    let merchant_key = parse_public_key(encryption_public_key)?;
+    let ephemeral = VasKey::generate()?;
+    let ephemeral_x = x_coordinate(&ephemeral.private_key).map_err(vas_key_error)?;
+    let shared_secret =
+        shared_secret(&ephemeral.private_key, &merchant_key).map_err(vas_key_error)?;
+    let key = derive_key(&shared_secret, &ephemeral_x);
+
+    let seconds = timestamp
+        .duration_since(UNIX_EPOCH + Duration::from_secs(APPLE_EPOCH))
+        .unwrap_or_default()
+        .as_secs() as u32;
+    let mut plaintext = seconds.to_be_bytes().to_vec();
+    plaintext.extend_from_slice(message.as_bytes());
+
+    let mut tag = [0; TAG_LEN];
+    let ciphertext = symm::encrypt_aead(
+        Cipher::aes_256_gcm(),
+        &key,
+        Some(&IV),
+        &[],
+        &plaintext,
+        &mut tag,
+    )
+    .map_err(vas_key_error)?;
+
+    let mut payload = key_id(&merchant_key).map_err(vas_key_error)?.to_vec();
+    payload.extend_from_slice(&ephemeral_x);
+    payload.extend_from_slice(&ciphertext);
+    payload.extend_from_slice(&tag);
+    Ok(payload)
+}
+
+fn x_coordinate<T: HasPublic>(key: &PKey<T>) -> Result<Vec<u8>, ErrorStack> {
+    let ec_key = key.ec_key()?;
+    let mut ctx = BigNumContext::new()?;
+    let mut x = BigNum::new()?;
+    let mut y = BigNum::new()?;
+    ec_key
+        .public_key()
+        .affine_coordinates(ec_key.group(), &mut x, &mut y, &mut ctx)?;
+    x.to_vec_padded(COORDINATE_LEN as i32)
+}
+
+fn key_id<T: HasPublic>(key: &PKey<T>) -> Result<[u8; 4], ErrorStack> {
+    let hash = sha256(&x_coordinate(key)?);
+    Ok([hash[0], hash[1], hash[2], hash[3]])
+}
+
+fn ephemeral_public_key(x: &[u8]) -> Result<PKey<Public>, ErrorStack> {
+    let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1)?;
+    let mut ctx = BigNumContext::new()?;
+    let mut compressed = vec![0x02];
+    compressed.extend_from_slice(x);
+    let point = EcPoint::from_bytes(&group, &compressed, &mut ctx)?;
+    PKey::from_ec_key(EcKey::from_public_key(&group, &point)?)
+}
+
+fn shared_secret(private_key: &PKey<Private>, peer: &PKey<Public>) -> Result<Vec<u8>, ErrorStack> {
+    let mut deriver = Deriver::new(private_key)?;
+    deriver.set_peer(peer)?;
+    deriver.derive_to_vec()
+}
+
+/// ANSI X9.63 key derivation with SHA-256, single block is enough for AES-256 key
+fn derive_key(shared_secret: &[u8], ephemeral_x: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(shared_secret);
+    hasher.update(&1u32.to_be_bytes());
+    hasher.update(SHARED_INFO);
+    hasher.update(ephemeral_x);
+    hasher.finish()
+}
+
+impl fmt::Debug for VasKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VasKey").finish()
+    }
+}
+
+/// Check that the value is a Base64 encoded SubjectPublicKeyInfo of P-256 key
+pub fn validate_encryption_public_key(key: &str) -> PassResult<()> {
+    parse_public_key(key).map(|_| ())
+}
+
+pub(crate) fn parse_public_key(key: &str) -> PassResult<PKey<Public>> {
+    let der = base64::decode_block(key.trim())
+        .map_err(|_| PassError::InvalidEncryptionPublicKey("not valid Base64"))?;
+    let public_key = PKey::public_key_from_der(&der)
+        .map_err(|_| PassError::InvalidEncryptionPublicKey("not X.509 SubjectPublicKeyInfo"))?;
+    check_curve(&public_key)?;
+    Ok(public_key)
+}
+
+fn check_curve<T: HasParams>(key: &PKey<T>) -> PassResult<()> {
+    let is_p256 = key.id() == Id::EC
+        && key.ec_key().ok().and_then(|key| key.group().curve_name())
+            == Some(Nid::X9_62_PRIME256V1);
+    if is_p256 {
+        Ok(())
+    } else {
+        Err(PassError::InvalidEncryptionPublicKey(
+            "ECDH key for group P-256 expected",
+        ))
+    }
+}
+
+fn vas_key_error(source: ErrorStack) -> PassError {
+    PassError::VasKey { source }
+}
+
+impl NFC {
+    /// Check encryption public key, if present
+    pub fn validate(&self) -> PassResult<()> {
+        match &self.encryption_public_key {
+            Some(key) => validate_encryption_public_key(key),
+            None => Ok(()),
+        }
+    }
+}
+
+mod test {
+    #[test]
+    fn generate_and_load_key() {
+        use super::*;
+
+        let key = VasKey::generate().unwrap();
+        let public_key = key.public_key().unwrap();
+        assert!(validate_encryption_public_key(&public_key).is_ok());
+
+        let loaded = VasKey::from_pem(&key.private_key_pem().unwrap()).unwrap();
+        assert_eq!(loaded.public_key().unwrap(), public_key);
+
+        let nfc = NFC {
+            message: "0001".into(),
+            encryption_public_key: Some(public_key),
+        };
+        assert!(nfc.validate().is_ok());
+    }
+
+    #[test]
+    fn reject_invalid_keys() {
+        use super::*;
+        use openssl::rsa::Rsa;
+
+        assert!(validate_encryption_public_key("not a key!").is_err());
+        assert!(validate_encryption_public_key("AAAA").is_err());
+
+        let rsa = PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap();
+        let der = rsa.public_key_to_der().unwrap();
+        assert!(validate_encryption_public_key(&base64::encode_block(&der)).is_err());
+
+        let group = EcGroup::from_curve_name(Nid::SECP384R1).unwrap();
+        let p384 = PKey::from_ec_key(EcKey::generate(&group).unwrap()).unwrap();
+        assert!(VasKey::from_pem(&p384.private_key_to_pem_pkcs8().unwrap()).is_err());
+    }
+
+    #[test]
+    fn decrypt_payload() {
+        use super::*;
+
+        let key = VasKey::generate().unwrap();
+        let timestamp = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let payload =
+            encrypt_vas_payload(&key.public_key().unwrap(), "member 0001", timestamp).unwrap();
+
+        let decrypted = key.decrypt(&payload).unwrap();
+        assert_eq!(decrypted.message, "member 0001");
+        assert_eq!(decrypted.timestamp, timestamp);
+
+        let other = VasKey::generate().unwrap();
+        assert!(other.decrypt(&payload).is_err());
+
+        let mut tampered = payload.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(key.decrypt(&tampered).is_err());
+        assert!(key.decrypt(&payload[..20]).is_err());
+    }
+}
@@ -0,0 +1,174 @@
+use glob::Pattern;
+use std::collections::HashSet;
+use std::fs;
+use std::path;
+use {PassError, PassResult};
+
+/// Rules to skip files of the source directory while building a pass
+///
+/// By default hidden files (`.DS_Store`, `.pass.json.swp`, …) and
+/// `manifest.json`/`signature` left from a previous build are ignored.
+#[derive(Debug, Clone)]
+pub struct IgnoreRules {
+    /// skip files and directories which names start with dot
+    hidden: bool,
+
+    /// glob patterns matched against path relative to the source directory, e.g. `*.psd`
+    patterns: Vec<Pattern>,
+}
+
+impl Default for IgnoreRules {
+    fn default() -> IgnoreRules {
+        IgnoreRules {
+            hidden: true,
+            patterns: vec![
+                Pattern::new("manifest.json").unwrap(),
+                Pattern::new("signature").unwrap(),
+            ],
+        }
+    }
+}
+
+impl IgnoreRules {
+    /// Rules that ignore nothing
+    pub fn none() -> IgnoreRules {
+        IgnoreRules {
+            hidden: false,
+            patterns: Vec::new(),
+        }
+    }
+
+    /// Also ignore files matching glob pattern
+    pub fn add_pattern(mut self, pattern: &str) -> PassResult<IgnoreRules> {
+        let compiled = Pattern::new(pattern).map_err(|source| PassError::IgnorePattern {
+            pattern: pattern.to_string(),
+            source,
+        })?;
+        self.patterns.push(compiled);
+        Ok(self)
+    }
+
+    /// Copy hidden files into the pass too
+    pub fn include_hidden(mut self) -> IgnoreRules {
+        self.hidden = false;
+        self
+    }
+
+    /// Check path relative to the source directory, with `/` as separator
+    pub fn is_ignored(&self, name: &str) -> bool {
+        let hidden = self.hidden && name.split('/').any(|part| part.starts_with('.'));
+        hidden || self.patterns.iter().any(|pattern| pattern.matches(name))
+    }
+}
+
+/// File found in the source directory
+#[derive(Debug, Clone)]
+pub(crate) struct SourceFile {
+    /// path relative to the source directory joined with `/`, as used in manifest.json
+    pub name: String,
+
+    /// location of the file to read from
+    pub path: path::PathBuf,
+}
+
+/// Recursively list files of the directory, skipping ignored ones.
+/// Symbolic links are followed only when they point inside of the directory.
+pub(crate) fn source_files(root: &path::Path, rules: &IgnoreRules) -> PassResult<Vec<SourceFile>> {
+    fn walk(
+        root: &path::Path,
+        dir: &path::Path,
+        prefix: &str,
+        rules: &IgnoreRules,
+        visited: &mut HashSet<path::PathBuf>,
+        files: &mut Vec<SourceFile>,
+    ) -> PassResult<()> {
+        let mut entries = fs::read_dir(dir)
+            .and_then(|entries| entries.collect::<Result<Vec<_>, _>>())
+            .map_err(PassError::read(dir))?;
+        entries.sort_by_key(|entry| entry.file_name());
+
+        for entry in entries {
+            let entry_path = entry.path();
+            let name = format!("{}{}", prefix, entry.file_name().to_string_lossy());
+            if rules.is_ignored(&name) {
+                continue;
+            }
+
+            let real_path = fs::canonicalize(&entry_path).map_err(PassError::read(&entry_path))?;
+            if !real_path.starts_with(root) {
+                return Err(PassError::SymlinkOutsideSource { path: entry_path });
+            }
+
+            if real_path.is_dir() {
+                // directory links inside of the source may lead to a loop
+                if visited.insert(real_path.clone()) {
+                    walk(
+                        root,
+                        &real_path,
+                        &format!("{}/", name),
+                        rules,
+                        visited,
+                        files,
+                    )?;
+                }
+            } else {
+                files.push(SourceFile {
+                    name,
+                    path: entry_path,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    let root = fs::canonicalize(root).map_err(PassError::read(root))?;
+    let mut visited = HashSet::new();
+    let mut files = Vec::new();
+    visited.insert(root.clone());
+    walk(&root, &root, "", rules, &mut visited, &mut files)?;
+    Ok(files)
+}
+
+mod test {
+    #[test]
+    fn default_rules() {
+        use super::*;
+
+        let rules = IgnoreRules::default();
+        assert!(rules.is_ignored(".DS_Store"));
+        assert!(rules.is_ignored("en.lproj/.pass.strings.swp"));
+        assert!(rules.is_ignored("manifest.json"));
+        assert!(rules.is_ignored("signature"));
+        assert!(!rules.is_ignored("en.lproj/logo@2x.png"));
+
+        let rules = rules.add_pattern("*.psd").unwrap();
+        assert!(rules.is_ignored("design/logo.psd"));
+        assert!(!IgnoreRules::none().is_ignored(".DS_Store"));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn nested_directories_and_symlinks() {
+        use super::*;
+        use std::os::unix::fs::symlink;
+        use tempdir::TempDir;
+
+        let outside = TempDir::new("outside").unwrap();
+        let source = TempDir::new("source").unwrap();
+        fs::create_dir(source.path().join("en.lproj")).unwrap();
+        fs::write(source.path().join("en.lproj/pass.strings"), "").unwrap();
+        fs::write(source.path().join("icon.png"), "").unwrap();
+        fs::write(source.path().join(".DS_Store"), "").unwrap();
+
+        let files = source_files(source.path(), &IgnoreRules::default()).unwrap();
+        let names: Vec<_> = files.iter().map(|file| file.name.as_str()).collect();
+        assert_eq!(names, vec!["en.lproj/pass.strings", "icon.png"]);
+
+        symlink(outside.path(), source.path().join("escape")).unwrap();
+        match source_files(source.path(), &IgnoreRules::default()) {
+            Err(PassError::SymlinkOutsideSource { path }) => assert!(path.ends_with("escape")),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use pass::Pass;
+use serde_derive::Deserialize;
+use {PassError, PassResult, Personalization, PersonalizationInfo, Signer};
+
+/// Body posted by Wallet to `/v1/passes/{passTypeIdentifier}/{serialNumber}/personalize`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PersonalizationBody {
+    /// Values user entered on the enrollment sheet
+    pub required_personalization_info: PersonalizationInfo,
+
+    /// Token to sign with the pass type certificate and return to Wallet
+    pub personalization_token: String,
+}
+
+/// Personalize request with pass identifiers taken from the path
+#[derive(Debug, Clone)]
+pub struct PersonalizeRequest {
+    pub pass_type_identifier: String,
+    pub serial_number: String,
+    pub info: PersonalizationInfo,
+    pub personalization_token: String,
+}
+
+impl PersonalizeRequest {
+    /// Parse request path and JSON body.
+    /// Path may contain prefix of the `webServiceURL`, e.g. `/passes/v1/passes/…/personalize`.
+    pub fn parse(path: &str, body: &[u8]) -> PassResult<PersonalizeRequest> {
+        let segments: Vec<&str> = path.trim_end_matches('/').rsplit('/').take(5).collect();
+        let (pass_type_identifier, serial_number) = match segments.as_slice() {
+            ["personalize", serial, type_id, "passes", "v1"]
+                if !serial.is_empty() && !type_id.is_empty() =>
+            {
+                (type_id.to_string(), serial.to_string())
+            }
+            _ => return Err(PassError::InvalidRequestPath(path.to_string())),
+        };
+
+        let request: PersonalizationBody =
+            serde_json::from_slice(body).map_err(|source| PassError::ParseRequest { source })?;
+
+        Ok(PersonalizeRequest {
+            pass_type_identifier,
+            serial_number,
+            info: request.required_personalization_info,
+            personalization_token: request.personalization_token,
+        })
+    }
+}
+
+/// Response to the personalize request
+#[derive(Debug)]
+pub struct Personalized {
+    /// Signed personalization token, send it back to Wallet as `application/octet-stream`
+    pub signature: Vec<u8>,
+
+    /// Personalized pass to replace the original one on the next pass request
+    pub pass: Pass,
+}
+
+/// Handle personalize request of Wallet.
+/// The callback receives user info and produces the personalized replacement pass.
+pub fn handle_personalize<F>(
+    path: &str,
+    body: &[u8],
+    personalization: &Personalization,
+    signer: &Signer,
+    personalize: F,
+) -> PassResult<Personalized>
+where
+    F: FnOnce(&PersonalizeRequest) -> Pass,
+{
+    let request = PersonalizeRequest::parse(path, body)?;
+    personalization.check_info(&request.info)?;
+
+    let signature = signer.sign(request.personalization_token.as_bytes())?;
+    let pass = personalize(&request);
+
+    Ok(Personalized { signature, pass })
+}
+
+mod test {
+    #[test]
+    fn personalize_request() {
+        use super::*;
+        use sign::test::self_signed;
+        use {PassBuilder, PersonalizationField};
+
+        let personalization = Personalization {
+            required_personalization_fields: vec![
+                PersonalizationField::Name,
+                PersonalizationField::EmailAddress,
+            ],
+            description: "Sign up and earn points.".to_string(),
+            terms_and_conditions: None,
+        };
+        let body = br#"{
+            "requiredPersonalizationInfo": {
+                "fullName": "John Appleseed",
+                "emailAddress": "john@example.com"
+            },
+            "personalizationToken": "A8B2C5"
+        }"#;
+        let path = "/passes/v1/passes/pass.com.example/0001/personalize";
+
+        let personalized =
+            handle_personalize(path, body, &personalization, &self_signed(), |request| {
+                assert_eq!(request.pass_type_identifier, "pass.com.example");
+                let name = request.info.value(PersonalizationField::Name).unwrap();
+                PassBuilder::new(
+                    request.serial_number.clone(),
+                    "pass.com.example",
+                    "CDHE9L6U22",
+                )
+                .add_primary_field(("member", "MEMBER", name.as_str()))
+                .finish_store_card()
+            })
+            .unwrap();
+
+        assert_eq!(personalized.pass.serial_number, "0001");
+        assert!(!personalized.signature.is_empty());
+
+        let incomplete = br#"{
+            "requiredPersonalizationInfo": { "fullName": "John Appleseed" },
+            "personalizationToken": "A8B2C5"
+        }"#;
+        let missing = handle_personalize(
+            path,
+            incomplete,
+            &personalization,
+            &self_signed(),
+            |_| unreachable!(),
+        );
+        match missing {
+            Err(PassError::MissingPersonalizationField(PersonalizationField::EmailAddress)) => {}
+            other => panic!("unexpected {:?}", other),
+        }
+
+        assert!(PersonalizeRequest::parse("/v1/passes/pass.com.example/0001", body).is_err());
+    }
+}